@@ -0,0 +1,56 @@
+//! Compares `is_valid_json` against `json(..).is_ok()` on the bundled sample document.
+//!
+//! Run with `cargo bench --bench is_valid_json`.
+
+use parser_combinator::json::{is_valid_json, json};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Allocator counting every allocation it performs.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const SAMPLE: &str = include_str!("../src/json/sample.json");
+const ITERATIONS: u32 = 200;
+
+fn measure(name: &str, validate: impl Fn(&str) -> bool) {
+    // Warm up lazily compiled regexes so they are not counted
+    assert!(validate(SAMPLE));
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        assert!(black_box(validate(black_box(SAMPLE))));
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{:<20} {:>10.1?}/iter {:>10} allocations/iter",
+        name,
+        elapsed / ITERATIONS,
+        allocations / ITERATIONS as usize
+    );
+}
+
+fn main() {
+    measure("json(..).is_ok()", |source| json(source).is_ok());
+    measure("is_valid_json", is_valid_json);
+}
@@ -1,6 +1,12 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 
-use crate::{ parsers::SourceRange, parsers::ToRangeOption };
+use crate::parsers::SourceRange;
+
+use super::display::Compact;
 
 /// Ast representation of a Json in parsed form
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -12,12 +18,16 @@ pub enum Json<'a> {
     Object {
         /// `object` representation
         elem: Vec<(Json<'a>, Json<'a>)>,
+        /// Slice of the source the object was parsed from, braces included
+        text: Verbatim<'a>,
     },
 
     /// Array contains consecutive Json value without a key.
     Array {
         /// `array` representation
         elem: Vec<Json<'a>>,
+        /// Slice of the source the array was parsed from, brackets included
+        text: Verbatim<'a>,
     },
 
     /// `number` terminal
@@ -45,44 +55,376 @@ pub enum Json<'a> {
         /// `null` representation
         elem: &'a str,
     },
+
+    /// Unparseable value, only produced when recovering from errors.
+    Error {
+        /// Slice of the source that could not be parsed
+        elem: &'a str,
+    },
+}
+
+/// Slice of the source a container was parsed from, None for containers built by hand.
+///
+/// Two containers holding the same values are equal whatever their layout in the source, so the
+/// slice is ignored when comparing, ordering and hashing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Verbatim<'a>(pub Option<&'a str>);
+
+impl PartialEq for Verbatim<'_> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Verbatim<'_> {}
+
+impl PartialOrd for Verbatim<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Verbatim<'_> {
+    fn cmp(&self, _: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Hash for Verbatim<'_> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
 }
 
 /// Showcase the implementation of the SourceRange trait.
 ///
+/// Objects and arrays span from their opening to their closing delimiter, as recorded by the
+/// parser. Those built by hand have no range.
+///
 /// # Note
 /// Not used in the current crate.
 impl<'a> SourceRange for Json<'a> {
     fn source_range(&self, source: &str) -> Option<Range<usize>> {
         match self {
-            Self::Object { elem } => {
-                let start = match elem.first() {
-                    Some(e) => e.0.source_range_start(source),
-                    None => None,
-                };
-
-                let end = match elem.last() {
-                    Some(e) => e.0.source_range_end(source),
-                    None => None,
-                };
-                (start..end).to_range()
-            }
-            Self::Array { elem } => {
-                let start = match elem.first() {
-                    Some(e) => e.source_range_start(source),
-                    None => None,
-                };
-
-                let end = match elem.last() {
-                    Some(e) => e.source_range_end(source),
-                    None => None,
-                };
-                (start..end).to_range()
-            }
+            Self::Object { text, .. } => text.0?.source_range(source),
+            Self::Array { text, .. } => text.0?.source_range(source),
             Self::Number { elem } => elem.source_range(source),
             Self::True { elem } => elem.source_range(source),
             Self::False { elem } => elem.source_range(source),
             Self::Null { elem } => elem.source_range(source),
             Self::String { elem } => elem.source_range(source),
+            Self::Error { elem } => elem.source_range(source),
+        }
+    }
+}
+
+/// Serializes the Json back into compact Json text.
+///
+/// Terminals are emitted verbatim from the source they were parsed from, which keeps the
+/// escape sequences of strings intact.
+///
+/// # Example
+/// ```
+/// use parser_combinator::json::*;
+///
+/// let parsed = json("{ \"a\" : [1, true,null] }").unwrap();
+/// assert_eq!("{\"a\":[1,true,null]}", parsed.to_string());
+/// ```
+///
+/// # Note
+/// `Error` nodes are emitted verbatim too, so a document parsed with `recover_errors` does not
+/// serialize back into valid Json.
+impl<'a> fmt::Display for Json<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Compact(self).fmt(f)
+    }
+}
+
+impl<'a> Json<'a> {
+    /// Number of direct children of the node.
+    ///
+    /// Counts the pairs of an object and the elements of an array. Terminal values have none.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// assert_eq!(2, json("[1, [2, 3]]").unwrap().entry_count());
+    /// assert_eq!(0, Json::Null { elem: "null" }.entry_count());
+    /// ```
+    pub fn entry_count(&self) -> usize {
+        match self {
+            Self::Object { elem, .. } => elem.len(),
+            Self::Array { elem, .. } => elem.len(),
+            _ => 0,
+        }
+    }
+
+    /// Number of pairs of an object or elements of an array.
+    ///
+    /// Returns None for terminal values, unlike `entry_count`.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// assert_eq!(Some(2), json("[1, [2, 3]]").unwrap().len());
+    /// assert_eq!(None, Json::Null { elem: "null" }.len());
+    /// ```
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Self::Object { elem, .. } => Some(elem.len()),
+            Self::Array { elem, .. } => Some(elem.len()),
+            _ => None,
+        }
+    }
+
+    /// Whether the node is an object or an array without any entry.
+    ///
+    /// Terminal values are never empty.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// assert!(json("{}").unwrap().is_empty());
+    /// assert!(!Json::String { elem: "" }.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    /// Byte length of the slice of the source the node was parsed from.
+    ///
+    /// Returns None if the node was not parsed from the given source.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let source = "1234";
+    /// let number = Json::Number { elem: &source[..] };
+    /// assert_eq!(Some(4), number.byte_len(source));
+    /// assert_eq!(None, number.byte_len(&source.to_string()));
+    /// ```
+    pub fn byte_len(&self, source: &str) -> Option<usize> {
+        self.source_range(source).map(|range| range.len())
+    }
+
+    /// Key value pairs of an object, in insertion order.
+    ///
+    /// Keys are decoded from their escape sequences, borrowing from the source when they hold none.
+    /// Returns None if the node is not an object or if one of its keys is not a string or holds an
+    /// invalid escape.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("{\"b\": 1, \"a\": 2}").unwrap();
+    /// let map = parsed.to_ordered_map().unwrap();
+    /// assert_eq!(vec!("b", "a"), map.iter().map(|(k, _)| k.as_ref()).collect::<Vec<_>>());
+    /// ```
+    pub fn to_ordered_map(&self) -> Option<Vec<(Cow<'a, str>, &Json<'a>)>> {
+        match self {
+            Self::Object { elem, .. } => elem
+                .iter()
+                .map(|(key, value)| Some((key.unescaped()?, value)))
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// Value associated to a key of an object.
+    ///
+    /// Keys are compared once their escape sequences are decoded. Returns None if the node is not
+    /// an object or if it has no such key. When a key is duplicated, the first pair wins.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("{\"a\": null}").unwrap();
+    /// assert_eq!(Some(&Json::Null { elem: "null" }), parsed.get("a"));
+    /// assert_eq!(None, parsed.get("b"));
+    /// ```
+    ///
+    /// # Note
+    /// Pairs are scanned in order, making the lookup O(n).
+    pub fn get(&self, key: &str) -> Option<&Json<'a>> {
+        match self {
+            Self::Object { elem, .. } => elem
+                .iter()
+                .find(|(k, _)| k.is_key(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Element of an array at the given position.
+    ///
+    /// Returns None if the node is not an array or if the index is out of range.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("[1, true]").unwrap();
+    /// assert_eq!(Some(&Json::True { elem: "true" }), parsed.index(1));
+    /// assert_eq!(None, parsed.index(2));
+    /// ```
+    pub fn index(&self, i: usize) -> Option<&Json<'a>> {
+        match self {
+            Self::Array { elem, .. } => elem.get(i),
+            _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{json, json_lenient};
+
+    #[test]
+    fn test_len() {
+        let object = json("{}").unwrap();
+        assert_eq!((Some(0), true), (object.len(), object.is_empty()));
+
+        let array = json("[1, \"two\", [3]]").unwrap();
+        assert_eq!((Some(3), false), (array.len(), array.is_empty()));
+        assert_eq!(Some(1), array.index(2).and_then(Json::len));
+
+        let number = Json::Number { elem: "42" };
+        assert_eq!((None, false), (number.len(), number.is_empty()));
+    }
+
+    #[test]
+    fn test_entry_count() {
+        let object = json("{\"a\": 1, \"b\": [1, 2, 3]}").unwrap();
+        assert_eq!(2, object.entry_count());
+
+        let array = json("[1, {\"a\": 1, \"b\": 2}, 3]").unwrap();
+        assert_eq!(3, array.entry_count());
+
+        assert_eq!(0, json("[]").unwrap().entry_count());
+        assert_eq!(0, Json::Number { elem: "42" }.entry_count());
+    }
+
+    #[test]
+    fn test_byte_len() {
+        let source = "[12.5, \"two words\", null]";
+        let parsed = json(source).unwrap();
+
+        let Json::Array { elem, .. } = &parsed else {
+            panic!("Expected an array");
+        };
+        assert_eq!(Some(4), elem[0].byte_len(source));
+        assert_eq!(Some(9), elem[1].byte_len(source));
+        assert_eq!(Some(4), elem[2].byte_len(source));
+
+        let copy = source.to_string();
+        assert_eq!(None, elem[0].byte_len(&copy));
+    }
+
+    #[test]
+    fn test_to_ordered_map() {
+        let parsed = json("{\"z\": 1, \"m\": [true], \"a\": null}").unwrap();
+        let map = parsed.to_ordered_map().unwrap();
+
+        let keys: Vec<&str> = map.iter().map(|(k, _)| k.as_ref()).collect();
+        assert_eq!(vec!("z", "m", "a"), keys);
+
+        let lookup = |key| map.iter().find(|(k, _)| k == key).map(|(_, v)| *v);
+        assert_eq!(Some(&Json::Null { elem: "null" }), lookup("a"));
+        assert_eq!(Some(1), lookup("m").map(Json::entry_count));
+        assert_eq!(None, lookup("b"));
+    }
+
+    #[test]
+    fn test_to_ordered_map_not_an_object() {
+        assert_eq!(None, json("[1]").unwrap().to_ordered_map());
+        assert_eq!(Some(vec!()), json("{}").unwrap().to_ordered_map());
+
+        let numeric_key = Json::Object {
+            elem: vec![(Json::Number { elem: "1" }, Json::Null { elem: "null" })],
+            text: Verbatim::default(),
+        };
+        assert_eq!(None, numeric_key.to_ordered_map());
+        assert_eq!(None, json("{\"\\ud800\": 1}").unwrap().to_ordered_map());
+    }
+
+    #[test]
+    fn test_to_ordered_map_escaped_keys() {
+        let parsed = json("{\"a\\/b\": 1, \"caf\\u00e9\": 2, \"plain\": 3}").unwrap();
+        let map = parsed.to_ordered_map().unwrap();
+
+        let lookup = |key| map.iter().find(|(k, _)| k == key).map(|(_, v)| *v);
+        assert_eq!(Some(&Json::Number { elem: "1" }), lookup("a/b"));
+        assert_eq!(Some(&Json::Number { elem: "2" }), lookup("caf\u{e9}"));
+        assert_eq!(None, lookup("a\\/b"));
+        assert!(matches!(map[2].0, Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn test_get() {
+        let parsed = json("{\"a\": 1, \"b\": {\"c\": []}, \"a\": 2}").unwrap();
+        assert_eq!(Some(&Json::Number { elem: "1" }), parsed.get("a"));
+        assert_eq!(Some(0), parsed.get("b").and_then(|b| b.get("c")).map(Json::entry_count));
+        assert_eq!(None, parsed.get("c"));
+        assert_eq!(None, json("[\"a\"]").unwrap().get("a"));
+
+        let escaped = json("{\"a\\/b\": true}").unwrap();
+        assert_eq!(Some(&Json::True { elem: "true" }), escaped.get("a/b"));
+        assert_eq!(None, escaped.get("a\\/b"));
+    }
+
+    #[test]
+    fn test_index() {
+        let parsed = json("[null, [1, 2]]").unwrap();
+        assert_eq!(Some(&Json::Null { elem: "null" }), parsed.index(0));
+        assert_eq!(
+            Some(&Json::Number { elem: "2" }),
+            parsed.index(1).and_then(|inner| inner.index(1))
+        );
+        assert_eq!(None, parsed.index(2));
+        assert_eq!(None, json("{\"0\": 1}").unwrap().index(0));
+    }
+
+    #[test]
+    fn test_source_text_containers() {
+        let source = "{\"a\": [ 1, {\"b\": \"x\"} ], \"c\": \"y\" }";
+        let parsed = json(source).unwrap();
+        assert_eq!(Some(source), parsed.source_text(source));
+
+        let array = parsed.get("a").unwrap();
+        assert_eq!(Some("[ 1, {\"b\": \"x\"} ]"), array.source_text(source));
+        assert_eq!(Some("{\"b\": \"x\"}"), array.index(1).unwrap().source_text(source));
+        assert_eq!(Some(0..source.len()), parsed.source_range(source));
+    }
+
+    #[test]
+    fn test_source_range_containers_without_range() {
+        let source = "[[], {}, [\"a\"]]";
+        let parsed = json(source).unwrap();
+        assert_eq!(Some("[]"), parsed.index(0).unwrap().source_text(source));
+        assert_eq!(Some("{}"), parsed.index(1).unwrap().source_text(source));
+        assert_eq!(Some("[\"a\"]"), parsed.index(2).unwrap().source_text(source));
+
+        let copy = source.to_string();
+        assert_eq!(None, parsed.index(2).unwrap().source_range(&copy));
+
+        let built = Json::Array {
+            elem: vec!(),
+            text: Verbatim::default(),
+        };
+        assert_eq!(None, built.source_range(source));
+        assert_eq!(parsed.index(0), Some(&built));
+    }
+
+    #[test]
+    fn test_source_range_containers_layout() {
+        let source = "{ // first\n\"a\": [1, 2,], /* last */ }";
+        let parsed = json_lenient(source).unwrap();
+        assert_eq!(Some(source), parsed.source_text(source));
+        assert_eq!(Some("[1, 2,]"), parsed.get("a").unwrap().source_text(source));
+    }
+}
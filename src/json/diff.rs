@@ -0,0 +1,220 @@
+use super::display::Compact;
+use super::pointer::escape_token;
+use super::*;
+use std::fmt;
+
+/// A single difference between two Json documents, located by a JSON pointer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonDiff<'s, 'a> {
+    /// The value only exists in the new document.
+    Added {
+        /// JSON pointer to the value.
+        path: String,
+        /// Added value.
+        value: &'s Json<'a>,
+    },
+
+    /// The value only exists in the old document.
+    Removed {
+        /// JSON pointer to the value.
+        path: String,
+        /// Removed value.
+        value: &'s Json<'a>,
+    },
+
+    /// The value exists in both documents but differs.
+    Changed {
+        /// JSON pointer to the value.
+        path: String,
+        /// Value in the old document.
+        from: &'s Json<'a>,
+        /// Value in the new document.
+        to: &'s Json<'a>,
+    },
+}
+
+/// Computes the differences needed to go from one document to the other.
+///
+/// Objects are compared key by key and arrays index by index. Numbers are compared by value so
+/// `1e2` and `100` are considered equal.
+///
+/// # Example
+/// ```
+/// use parser_combinator::json::*;
+///
+/// let from = json("{\"a\": 1, \"b\": 2}").unwrap();
+/// let to = json("{\"a\": 1e0, \"b\": 3}").unwrap();
+/// assert_eq!(
+///     vec!(JsonDiff::Changed {
+///         path: "/b".to_string(),
+///         from: &Json::Number { elem: "2" },
+///         to: &Json::Number { elem: "3" },
+///     }),
+///     json_diff(&from, &to)
+/// );
+/// ```
+pub fn json_diff<'s, 'a>(from: &'s Json<'a>, to: &'s Json<'a>) -> Vec<JsonDiff<'s, 'a>> {
+    let mut diffs = Vec::new();
+    diff_into(from, to, String::new(), &mut diffs);
+    diffs
+}
+
+fn diff_into<'s, 'a>(
+    from: &'s Json<'a>,
+    to: &'s Json<'a>,
+    path: String,
+    diffs: &mut Vec<JsonDiff<'s, 'a>>,
+) {
+    match (from, to) {
+        (Json::Object { elem: old, .. }, Json::Object { elem: new, .. }) => {
+            let child = |key: &Json| match key {
+                Json::String { elem } => format!("{}/{}", path, escape_token(elem)),
+                _ => format!("{}/{}", path, escape_token(&Compact(key).to_string())),
+            };
+
+            for (key, value) in old {
+                match new.iter().find(|(k, _)| k == key) {
+                    Some((_, new_value)) => diff_into(value, new_value, child(key), diffs),
+                    None => diffs.push(JsonDiff::Removed {
+                        path: child(key),
+                        value,
+                    }),
+                }
+            }
+
+            for (key, value) in new {
+                if !old.iter().any(|(k, _)| k == key) {
+                    diffs.push(JsonDiff::Added {
+                        path: child(key),
+                        value,
+                    });
+                }
+            }
+        }
+        (Json::Array { elem: old, .. }, Json::Array { elem: new, .. }) => {
+            for (idx, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                diff_into(old_value, new_value, format!("{}/{}", path, idx), diffs);
+            }
+
+            for (idx, value) in old.iter().enumerate().skip(new.len()) {
+                diffs.push(JsonDiff::Removed {
+                    path: format!("{}/{}", path, idx),
+                    value,
+                });
+            }
+
+            for (idx, value) in new.iter().enumerate().skip(old.len()) {
+                diffs.push(JsonDiff::Added {
+                    path: format!("{}/{}", path, idx),
+                    value,
+                });
+            }
+        }
+        (Json::Number { .. }, Json::Number { .. })
+            if from.normalized_number().is_some()
+                && from.normalized_number() == to.normalized_number() => {}
+        _ if from == to => {}
+        _ => diffs.push(JsonDiff::Changed { path, from, to }),
+    }
+}
+
+/// Renders the differences between two documents as human readable text.
+///
+/// Each line starts with `+` for added values, `-` for removed values and `~` for changed ones,
+/// followed by the JSON pointer of the value. The root of the document is shown as `(root)`.
+///
+/// # Example
+/// ```
+/// use parser_combinator::json::*;
+///
+/// let from = json("[1, 2]").unwrap();
+/// let to = json("[1, 2, {\"a\": null}]").unwrap();
+/// assert_eq!("+ /2: {\"a\":null}\n", render_diff(&from, &to));
+/// ```
+pub fn render_diff(from: &Json, to: &Json) -> String {
+    json_diff(from, to)
+        .iter()
+        .map(|diff| format!("{}\n", diff))
+        .collect()
+}
+
+impl<'s, 'a> fmt::Display for JsonDiff<'s, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn shown(path: &str) -> &str {
+            if path.is_empty() {
+                "(root)"
+            } else {
+                path
+            }
+        }
+
+        match self {
+            Self::Added { path, value } => write!(f, "+ {}: {}", shown(path), Compact(value)),
+            Self::Removed { path, value } => write!(f, "- {}: {}", shown(path), Compact(value)),
+            Self::Changed { path, from, to } => {
+                write!(f, "~ {}: {} -> {}", shown(path), Compact(from), Compact(to))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_diff_same() {
+        let from = json("{\"a\": [1, {\"b\": 1e2}], \"c\": \"d\"}").unwrap();
+        let to = json("{\"c\": \"d\", \"a\": [1.0, {\"b\": 100}]}").unwrap();
+        assert_eq!(Vec::<JsonDiff>::new(), json_diff(&from, &to));
+    }
+
+    #[test]
+    fn test_json_diff_variant_change() {
+        let from = json("[1]").unwrap();
+        let to = json("{}").unwrap();
+        assert_eq!(
+            vec!(JsonDiff::Changed {
+                path: "".to_string(),
+                from: &from,
+                to: &to
+            }),
+            json_diff(&from, &to)
+        );
+    }
+
+    #[test]
+    fn test_json_diff_escaped_path() {
+        let from = json("{\"a/b\": {\"~\": 1}}").unwrap();
+        let to = json("{\"a/b\": {}}").unwrap();
+        assert_eq!(
+            vec!(JsonDiff::Removed {
+                path: "/a~1b/~0".to_string(),
+                value: &Json::Number { elem: "1" }
+            }),
+            json_diff(&from, &to)
+        );
+    }
+
+    #[test]
+    fn test_render_diff() {
+        let from = json("{\"name\": \"bob\", \"tags\": [\"a\", \"b\"], \"age\": 30}").unwrap();
+        let to =
+            json("{\"name\": \"bob\", \"tags\": [\"a\"], \"age\": 31, \"admin\": true}").unwrap();
+
+        assert_eq!(
+            "- /tags/1: \"b\"\n\
+            ~ /age: 30 -> 31\n\
+            + /admin: true\n",
+            render_diff(&from, &to)
+        );
+    }
+
+    #[test]
+    fn test_render_diff_root() {
+        let from = json("[]").unwrap();
+        let to = json("{}").unwrap();
+        assert_eq!("~ (root): [] -> {}\n", render_diff(&from, &to));
+        assert_eq!("", render_diff(&from, &from));
+    }
+}
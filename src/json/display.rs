@@ -0,0 +1,193 @@
+use super::*;
+use std::fmt;
+
+/// Compact Json text of a node, shared by `Display` and the rendering of diffs.
+///
+/// Terminals are emitted verbatim from the source they were parsed from, which keeps the
+/// escape sequences of strings intact. `Error` nodes are emitted verbatim as well, the output
+/// is then not valid Json.
+pub(crate) struct Compact<'j, 'a>(pub(crate) &'j Json<'a>);
+
+impl fmt::Display for Compact<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Json::Object { elem, .. } => {
+                write!(f, "{{")?;
+                for (idx, (key, value)) in elem.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{}", Compact(key), Compact(value))?;
+                }
+                write!(f, "}}")
+            }
+            Json::Array { elem, .. } => {
+                write!(f, "[")?;
+                for (idx, value) in elem.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", Compact(value))?;
+                }
+                write!(f, "]")
+            }
+            Json::String { elem } => write!(f, "\"{}\"", elem),
+            Json::Number { elem }
+            | Json::True { elem }
+            | Json::False { elem }
+            | Json::Null { elem }
+            | Json::Error { elem } => write!(f, "{}", elem),
+        }
+    }
+}
+
+impl<'a> Json<'a> {
+    /// Serializes the Json into human readable Json text.
+    ///
+    /// Every array and object entry goes on its own line, indented by `indent` spaces per level
+    /// of nesting. Empty arrays and objects stay on a single line.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("{\"a\": [1, {}]}").unwrap();
+    /// assert_eq!("{\n  \"a\": [\n    1,\n    {}\n  ]\n}", parsed.to_pretty_string(2));
+    /// ```
+    pub fn to_pretty_string(&self, indent: usize) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, indent, 0);
+        out
+    }
+
+    /// Appends the pretty representation of the Json, nested `depth` levels deep.
+    fn write_pretty(&self, out: &mut String, indent: usize, depth: usize) {
+        let padding = |depth: usize| " ".repeat(indent * depth);
+
+        match self {
+            Self::Object { elem, .. } if !elem.is_empty() => {
+                out.push_str("{\n");
+                for (idx, (key, value)) in elem.iter().enumerate() {
+                    if idx > 0 {
+                        out.push_str(",\n");
+                    }
+                    out.push_str(&padding(depth + 1));
+                    out.push_str(&format!("{}: ", key));
+                    value.write_pretty(out, indent, depth + 1);
+                }
+                out.push('\n');
+                out.push_str(&padding(depth));
+                out.push('}');
+            }
+            Self::Array { elem, .. } if !elem.is_empty() => {
+                out.push_str("[\n");
+                for (idx, value) in elem.iter().enumerate() {
+                    if idx > 0 {
+                        out.push_str(",\n");
+                    }
+                    out.push_str(&padding(depth + 1));
+                    value.write_pretty(out, indent, depth + 1);
+                }
+                out.push('\n');
+                out.push_str(&padding(depth));
+                out.push(']');
+            }
+            // Terminals and empty containers have the same compact representation
+            _ => out.push_str(&self.to_string()),
+        }
+    }
+}
+
+/// Minifies Json text by parsing it and emitting it back in compact form.
+///
+/// Going through the parser guarantees that the output is valid Json.
+///
+/// # Example
+/// ```
+/// use parser_combinator::json::*;
+///
+/// assert_eq!(Ok("{\"a\":[1,2]}".to_string()), minify("{\n  \"a\": [\n    1,\n    2\n  ]\n}"));
+/// assert!(minify("{\"a\": }").is_err());
+/// ```
+pub fn minify(source: &str) -> Result<String, String> {
+    json(source).map(|parsed| Compact(&parsed).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_terminals() {
+        let compact = |json: Json| Compact(&json).to_string();
+        assert_eq!("\"a\\\"b\"", compact(Json::String { elem: "a\\\"b" }));
+        assert_eq!("-1.5e3", compact(Json::Number { elem: "-1.5e3" }));
+        assert_eq!("null", compact(Json::Null { elem: "null" }));
+    }
+
+    #[test]
+    fn test_display_containers() {
+        let compact = |source| Compact(&json(source).unwrap()).to_string();
+        assert_eq!("{}", compact("{ }"));
+        assert_eq!("[]", compact("[ ]"));
+        assert_eq!(
+            "[{\"a\":{\"b\":[]}},false]",
+            compact("[ {\"a\": {\"b\": [ ]}} , false ]")
+        );
+    }
+
+    #[test]
+    fn test_display_error_nodes() {
+        let options = JsonOptions {
+            recover_errors: true,
+            ..JsonOptions::default()
+        };
+        let rendered = json_with("[1, @@@, 3]", &options).unwrap().to_string();
+        assert_eq!("[1,@@@,3]", rendered);
+        assert!(json(&rendered).is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let parsed = json(include_str!("sample.json")).unwrap();
+        assert_eq!(Ok(parsed.clone()), json(&parsed.to_string()));
+    }
+
+    #[test]
+    fn test_pretty_string() {
+        let parsed = json("{\"a\": {\"b\": []}, \"c\": [1, \"d\"]}").unwrap();
+        assert_eq!(
+            "{\n    \"a\": {\n        \"b\": []\n    },\n    \"c\": [\n        1,\n        \
+            \"d\"\n    ]\n}",
+            parsed.to_pretty_string(4)
+        );
+        assert_eq!("{}", json("{ }").unwrap().to_pretty_string(2));
+    }
+
+    #[test]
+    fn test_pretty_string_round_trip() {
+        let parsed = json(include_str!("sample.json")).unwrap();
+        assert_eq!(Ok(parsed.clone()), json(&parsed.to_pretty_string(2)));
+        assert_eq!(Ok(parsed.clone()), json(&parsed.to_pretty_string(0)));
+    }
+
+    #[test]
+    fn test_minify() {
+        let source = "{\n    \"name\": \"two words\",\n    \"tags\": [\n        \"a\",\n        \
+            \"b\"\n    ],\n    \"nested\": {\n        \"empty\": {}\n    }\n}\n";
+        assert_eq!(
+            Ok(
+                "{\"name\":\"two words\",\"tags\":[\"a\",\"b\"],\"nested\":{\"empty\":{}}}"
+                    .to_string()
+            ),
+            minify(source)
+        );
+    }
+
+    #[test]
+    fn test_minify_invalid() {
+        assert!(minify("").is_err());
+        assert!(minify("[1, 2,]").is_err());
+        assert!(minify("{\"a\" 1}").is_err());
+    }
+}
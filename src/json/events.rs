@@ -0,0 +1,319 @@
+use super::parser::{string, terminal_value};
+use super::*;
+use crate::parsers::*;
+
+/// Event emitted by [`json_events`] while going through a Json document.
+///
+/// Terminals hold the same slices as their [`Json`] counterpart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonEvent<'a> {
+    /// Opening `{` of an object.
+    StartObject,
+    /// Closing `}` of an object.
+    EndObject,
+    /// Opening `[` of an array.
+    StartArray,
+    /// Closing `]` of an array.
+    EndArray,
+    /// Key of an object pair, its value comes as the next event.
+    Key(&'a str),
+    /// `string` terminal
+    String(&'a str),
+    /// `number` terminal
+    Number(&'a str),
+    /// `true` terminal
+    True,
+    /// `false` terminal
+    False,
+    /// `null` terminal
+    Null,
+}
+
+/// Kind of container the events are currently nested in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Object,
+    Array,
+}
+
+/// What the next token of the document is expected to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    /// The top level object or array.
+    Root,
+    /// A key, or the end of the object if it is still empty.
+    KeyOrEnd,
+    /// A key, following a comma.
+    Key,
+    /// A value, or the end of the array if it is still empty.
+    ValueOrEnd,
+    /// A value, following a comma or a colon.
+    Value,
+    /// A comma, or the end of the enclosing container.
+    CommaOrEnd,
+    /// Nothing but whitespace, the top level value being closed.
+    Eof,
+}
+
+/// Iterator behind [`json_events`].
+struct JsonEvents<'a> {
+    remainder: &'a str,
+    containers: Vec<Container>,
+    expect: Expect,
+    done: bool,
+}
+
+impl<'a> JsonEvents<'a> {
+    /// Emits the event for an opening bracket, if the remainder starts with one.
+    fn open(&mut self) -> Option<JsonEvent<'a>> {
+        let (container, event, expect) = match self.remainder.as_bytes().first() {
+            Some(b'{') => (Container::Object, JsonEvent::StartObject, Expect::KeyOrEnd),
+            Some(b'[') => (Container::Array, JsonEvent::StartArray, Expect::ValueOrEnd),
+            _ => return None,
+        };
+
+        self.remainder = &self.remainder[1..];
+        self.containers.push(container);
+        self.expect = expect;
+        Some(event)
+    }
+
+    /// Emits the event for the closing bracket of the current container, if the remainder
+    /// starts with it.
+    fn close(&mut self) -> Option<JsonEvent<'a>> {
+        let event = match (self.containers.last(), self.remainder.as_bytes().first()) {
+            (Some(Container::Object), Some(b'}')) => JsonEvent::EndObject,
+            (Some(Container::Array), Some(b']')) => JsonEvent::EndArray,
+            _ => return None,
+        };
+
+        self.remainder = &self.remainder[1..];
+        self.containers.pop();
+        self.expect = match self.containers.is_empty() {
+            true => Expect::Eof,
+            false => Expect::CommaOrEnd,
+        };
+        Some(event)
+    }
+
+    /// Parses the next token, returning None at the end of the document.
+    fn step(&mut self) -> Result<Option<JsonEvent<'a>>, String> {
+        // Like `json`, whitespace is not allowed before the top level value
+        if self.expect != Expect::Root {
+            self.remainder = maybe(whitespace).parse(self.remainder)?.0;
+        }
+
+        match self.expect {
+            Expect::Root => self
+                .open()
+                .map(Some)
+                .ok_or_else(|| "Expected an object or an array".to_string()),
+
+            Expect::KeyOrEnd | Expect::Key => {
+                if self.expect == Expect::KeyOrEnd {
+                    if let Some(event) = self.close() {
+                        return Ok(Some(event));
+                    }
+                }
+
+                let key = left(string(), maybe(whitespace));
+                let key = left(key, literal(":"));
+                let (remainder, key) = key.parse(self.remainder)?;
+                self.remainder = remainder;
+                self.expect = Expect::Value;
+                match key {
+                    Json::String { elem } => Ok(Some(JsonEvent::Key(elem))),
+                    _ => unreachable!("The string parser only yields strings"),
+                }
+            }
+
+            Expect::ValueOrEnd | Expect::Value => {
+                if self.expect == Expect::ValueOrEnd {
+                    if let Some(event) = self.close() {
+                        return Ok(Some(event));
+                    }
+                }
+                if let Some(event) = self.open() {
+                    return Ok(Some(event));
+                }
+
+                let (remainder, value) = terminal_value().parse(self.remainder)?;
+                self.remainder = remainder;
+                self.expect = Expect::CommaOrEnd;
+                Ok(Some(match value {
+                    Json::String { elem } => JsonEvent::String(elem),
+                    Json::Number { elem } => JsonEvent::Number(elem),
+                    Json::True { .. } => JsonEvent::True,
+                    Json::False { .. } => JsonEvent::False,
+                    Json::Null { .. } => JsonEvent::Null,
+                    _ => unreachable!("Terminal values are never containers nor errors"),
+                }))
+            }
+
+            Expect::CommaOrEnd => {
+                if let Some(event) = self.close() {
+                    return Ok(Some(event));
+                }
+
+                self.remainder = literal::<_, String>(",").parse(self.remainder)?.0;
+                self.expect = match self.containers.last() {
+                    Some(Container::Object) => Expect::Key,
+                    _ => Expect::Value,
+                };
+                self.step()
+            }
+
+            Expect::Eof => match self.remainder {
+                "" => Ok(None),
+                rem => {
+                    let snippet: String = rem.chars().take(16).collect();
+                    Err(format!("Unexpected trailing characters '{}'", snippet))
+                }
+            },
+        }
+    }
+}
+
+impl<'a> Iterator for JsonEvents<'a> {
+    type Item = Result<JsonEvent<'a>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let event = self.step().transpose();
+        // Stop at the first error, the state of the document is unknown past it
+        self.done = !matches!(event, Some(Ok(_)));
+        event
+    }
+}
+
+/// Goes through a slice representing Json, emitting an event for each token instead of
+/// building a [`Json`].
+///
+/// Accepts the same grammar as [`json`]. Nesting is tracked on the heap, so arbitrarily deep
+/// documents do not overflow the stack.
+///
+/// # Result Conditions
+/// Yields a single error when the document turns out to be invalid, and stops right after.
+///
+/// # Example
+/// ```
+/// use parser_combinator::json::*;
+///
+/// let events: Vec<_> = json_events("{\"a\": [1, null]}").collect();
+/// assert_eq!(
+///     vec!(
+///         Ok(JsonEvent::StartObject),
+///         Ok(JsonEvent::Key("a")),
+///         Ok(JsonEvent::StartArray),
+///         Ok(JsonEvent::Number("1")),
+///         Ok(JsonEvent::Null),
+///         Ok(JsonEvent::EndArray),
+///         Ok(JsonEvent::EndObject),
+///     ),
+///     events
+/// );
+/// ```
+pub fn json_events(source: &str) -> impl Iterator<Item = Result<JsonEvent<'_>, String>> {
+    JsonEvents {
+        remainder: source,
+        containers: Vec::new(),
+        expect: Expect::Root,
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Events expected for a tree, built from the parsed representation.
+    fn tree_events<'a>(json: &Json<'a>, events: &mut Vec<JsonEvent<'a>>) {
+        match json {
+            Json::Object { elem, .. } => {
+                events.push(JsonEvent::StartObject);
+                for (key, value) in elem {
+                    if let Json::String { elem } = key {
+                        events.push(JsonEvent::Key(elem));
+                    }
+                    tree_events(value, events);
+                }
+                events.push(JsonEvent::EndObject);
+            }
+            Json::Array { elem, .. } => {
+                events.push(JsonEvent::StartArray);
+                elem.iter().for_each(|value| tree_events(value, events));
+                events.push(JsonEvent::EndArray);
+            }
+            Json::String { elem } => events.push(JsonEvent::String(elem)),
+            Json::Number { elem } => events.push(JsonEvent::Number(elem)),
+            Json::True { .. } => events.push(JsonEvent::True),
+            Json::False { .. } => events.push(JsonEvent::False),
+            Json::Null { .. } => events.push(JsonEvent::Null),
+            Json::Error { .. } => panic!("Unexpected error node"),
+        }
+    }
+
+    #[test]
+    fn test_json_events_sample() {
+        let source = include_str!("sample.json");
+        let mut expected = Vec::new();
+        tree_events(&json(source).unwrap(), &mut expected);
+
+        let events: Result<Vec<_>, _> = json_events(source).collect();
+        assert_eq!(Ok(expected), events);
+    }
+
+    #[test]
+    fn test_json_events_sequence() {
+        let events: Vec<_> = json_events("[ {}, [], \"s\", -1.5 , true, false ] ").collect();
+        assert_eq!(
+            vec![
+                Ok(JsonEvent::StartArray),
+                Ok(JsonEvent::StartObject),
+                Ok(JsonEvent::EndObject),
+                Ok(JsonEvent::StartArray),
+                Ok(JsonEvent::EndArray),
+                Ok(JsonEvent::String("s")),
+                Ok(JsonEvent::Number("-1.5")),
+                Ok(JsonEvent::True),
+                Ok(JsonEvent::False),
+                Ok(JsonEvent::EndArray),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn test_json_events_malformed() {
+        let mut events = json_events("{\"a\": 1 \"b\": 2}");
+        assert_eq!(Some(Ok(JsonEvent::StartObject)), events.next());
+        assert_eq!(Some(Ok(JsonEvent::Key("a"))), events.next());
+        assert_eq!(Some(Ok(JsonEvent::Number("1"))), events.next());
+        assert!(matches!(events.next(), Some(Err(_))));
+        assert_eq!(None, events.next());
+
+        let inputs = [
+            "",
+            " []",
+            "[1,]",
+            "[1 2]",
+            "{\"a\" 1}",
+            "{1: 2}",
+            "[]]",
+            "[] x",
+        ];
+        for input in inputs {
+            let last = json_events(input).last();
+            assert!(matches!(last, Some(Err(_))), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_json_events_deeply_nested() {
+        let source = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        assert_eq!(200_000, json_events(&source).filter(Result::is_ok).count());
+    }
+}
@@ -0,0 +1,115 @@
+use super::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+impl<'a> Json<'a> {
+    /// Hash of the canonical form of the node.
+    ///
+    /// Unlike the derived `Hash`, the key order of objects and the textual form of numbers and
+    /// strings do not matter, so semantically equal documents hash identically.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let a = json("{\"a\": 1e2, \"b\": [true]}").unwrap();
+    /// let b = json("{\"b\": [true], \"a\": 100}").unwrap();
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    /// ```
+    ///
+    /// # Note
+    /// The hash is only stable for a given build of the crate, it should not be persisted.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash_canonical(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Feeds the canonical form of the node into the hasher.
+    fn hash_canonical<H: Hasher>(&self, state: &mut H) {
+        // Variant tags keep `[]` and `{}` (or `"1"` and `1`) apart
+        match self {
+            Self::Object { elem, .. } => {
+                // Pairs are hashed independently then sorted so that order does not matter
+                let mut pairs: Vec<u64> = elem
+                    .iter()
+                    .map(|(key, value)| {
+                        let mut hasher = DefaultHasher::new();
+                        key.hash_canonical(&mut hasher);
+                        value.hash_canonical(&mut hasher);
+                        hasher.finish()
+                    })
+                    .collect();
+                pairs.sort_unstable();
+
+                0u8.hash(state);
+                pairs.hash(state);
+            }
+            Self::Array { elem, .. } => {
+                1u8.hash(state);
+                elem.len().hash(state);
+                elem.iter().for_each(|e| e.hash_canonical(state));
+            }
+            Self::Number { elem } => {
+                2u8.hash(state);
+                match self.normalized_number() {
+                    Some(normalized) => normalized.hash(state),
+                    None => elem.hash(state),
+                }
+            }
+            Self::String { elem } => {
+                3u8.hash(state);
+                match self.unescaped() {
+                    Some(decoded) => decoded.hash(state),
+                    None => elem.hash(state),
+                }
+            }
+            Self::True { .. } => 4u8.hash(state),
+            Self::False { .. } => 5u8.hash(state),
+            Self::Null { .. } => 6u8.hash(state),
+            Self::Error { elem } => {
+                7u8.hash(state);
+                elem.hash(state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(source: &str) -> u64 {
+        json(source).unwrap().content_hash()
+    }
+
+    #[test]
+    fn test_content_hash_key_order() {
+        assert_eq!(
+            hash("{\"a\": 1, \"b\": {\"c\": null, \"d\": false}}"),
+            hash("{\"b\": {\"d\": false, \"c\": null}, \"a\": 1}")
+        );
+    }
+
+    #[test]
+    fn test_content_hash_number_format() {
+        assert_eq!(hash("[1e2, 0.5]"), hash("[100.00, 5e-1]"));
+    }
+
+    #[test]
+    fn test_content_hash_string_escapes() {
+        assert_eq!(hash("[\"\\u0041\"]"), hash("[\"A\"]"));
+        assert_eq!(hash("{\"a\\/b\": 1}"), hash("{\"a/b\": 1}"));
+        assert_ne!(hash("[\"\\u0041\"]"), hash("[\"a\"]"));
+    }
+
+    #[test]
+    fn test_content_hash_different() {
+        assert_ne!(hash("{\"a\": 1}"), hash("{\"a\": 2}"));
+        assert_ne!(hash("{\"a\": 1}"), hash("{\"b\": 1}"));
+        assert_ne!(hash("[1, 2]"), hash("[2, 1]"));
+        assert_ne!(hash("[\"1\"]"), hash("[1]"));
+        assert_ne!(hash("[[]]"), hash("[{}]"));
+        assert_ne!(hash("[true]"), hash("[false]"));
+    }
+}
@@ -1,5 +1,28 @@
 mod ast;
+mod diff;
+mod display;
+mod events;
+mod hash;
+mod number;
+mod owned;
 mod parser;
+mod patch;
+mod pointer;
+mod query;
+mod recognize;
+mod traversal;
+mod unescape;
+mod validation;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "yaml")]
+mod yaml;
 
-pub use parser::json;
-pub use ast::Json;
+pub use parser::{json, json_lenient, json_typed, json_with, JsonOptions};
+pub use ast::{Json, Verbatim};
+pub use diff::{json_diff, render_diff, JsonDiff};
+pub use display::minify;
+pub use events::{json_events, JsonEvent};
+pub use owned::{JsonOwned, OwnedJson, REDACTED};
+pub use recognize::is_valid_json;
+pub use validation::ValidationError;
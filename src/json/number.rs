@@ -0,0 +1,221 @@
+use super::*;
+
+impl<'a> Json<'a> {
+    /// Raw text of a `number` terminal, exactly as it appears in the source.
+    ///
+    /// Unlike the other number accessors, nothing goes through an `f64`, so no precision is lost.
+    /// This is the one to use with arbitrary precision decimals. Returns None for any other
+    /// variant.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let number = Json::Number { elem: "1.50E+3" };
+    /// assert_eq!(Some("1.50E+3"), number.as_number_str());
+    /// assert_eq!(None, Json::String { elem: "1" }.as_number_str());
+    /// ```
+    pub fn as_number_str(&self) -> Option<&'a str> {
+        match self {
+            Self::Number { elem } => Some(elem),
+            _ => None,
+        }
+    }
+
+    /// Canonical decimal representation of a `number` terminal.
+    ///
+    /// Follows the ECMAScript `Number::toString` rules, as required by RFC 8785, so that every
+    /// textual form of the same number yields the same string. Returns None for any other variant
+    /// or if the number does not fit in a finite `f64`.
+    ///
+    /// # Note
+    /// The value is lossy: digits beyond the precision of an `f64` are dropped. Use
+    /// `as_number_str` when the exact text matters.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let number = Json::Number { elem: "1.50E+3" };
+    /// assert_eq!(Some("1500".to_string()), number.normalized_number());
+    /// assert_eq!(None, Json::Null { elem: "null" }.normalized_number());
+    /// ```
+    pub fn normalized_number(&self) -> Option<String> {
+        let value: f64 = match self {
+            Self::Number { elem } => elem.parse().ok()?,
+            _ => return None,
+        };
+
+        if !value.is_finite() {
+            return None;
+        }
+
+        // Covers -0 as well
+        if value == 0.0 {
+            return Some("0".to_string());
+        }
+
+        // Rust yields the shortest round-tripping digits, which is what ECMAScript mandates
+        let scientific = format!("{:e}", value.abs());
+        let (mantissa, exponent) = scientific.split_once('e')?;
+        let digits = mantissa.replace('.', "");
+        let k = digits.len() as i32;
+        let n = exponent.parse::<i32>().ok()? + 1;
+
+        let sign = if value < 0.0 { "-" } else { "" };
+        let body = if k <= n && n <= 21 {
+            format!("{}{}", digits, "0".repeat((n - k) as usize))
+        } else if 0 < n && n <= 21 {
+            format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+        } else if -6 < n && n <= 0 {
+            format!("0.{}{}", "0".repeat(-n as usize), digits)
+        } else {
+            let exponent_sign = if n - 1 < 0 { "-" } else { "+" };
+            let fraction = match &digits[1..] {
+                "" => String::new(),
+                rest => format!(".{}", rest),
+            };
+            format!(
+                "{}{}e{}{}",
+                &digits[..1],
+                fraction,
+                exponent_sign,
+                (n - 1).abs()
+            )
+        };
+
+        Some(format!("{}{}", sign, body))
+    }
+
+    /// Value of a `number` terminal as an `f64`.
+    ///
+    /// Returns None for any other variant or if the number does not fit in a finite `f64`.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// assert_eq!(Some(-1500.0), Json::Number { elem: "-1.5E+3" }.as_f64());
+    /// assert_eq!(None, Json::String { elem: "1" }.as_f64());
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number { elem } => elem.parse().ok().filter(|value: &f64| value.is_finite()),
+            _ => None,
+        }
+    }
+
+    /// Value of a `number` terminal as an `i64`.
+    ///
+    /// Numbers written with a fraction or an exponent are accepted as long as their value is a
+    /// whole number, so `1e3` and `2.0` both convert. Returns None for any other variant or if the
+    /// value has a fractional part or is out of range.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// assert_eq!(Some(1000), Json::Number { elem: "1e3" }.as_i64());
+    /// assert_eq!(None, Json::Number { elem: "0.64" }.as_i64());
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        let elem = match self {
+            Self::Number { elem } => elem,
+            _ => return None,
+        };
+
+        // Plain integers are parsed directly so that no precision is lost
+        if let Ok(value) = elem.parse() {
+            return Some(value);
+        }
+
+        let value = self.as_f64()?;
+        let in_range = value >= i64::MIN as f64 && value < i64::MAX as f64;
+        (value.fract() == 0.0 && in_range).then_some(value as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalized(elem: &str) -> Option<String> {
+        Json::Number { elem }.normalized_number()
+    }
+
+    #[test]
+    fn test_as_number_str_exact() {
+        let big = "123456789012345678901234567890";
+        let source = format!("[{}]", big);
+        let parsed = json(&source).unwrap();
+        let Json::Array { elem, .. } = &parsed else {
+            panic!("Expected an array")
+        };
+
+        assert_eq!(Some(big), elem[0].as_number_str());
+        assert_ne!(Some(big.to_string()), elem[0].normalized_number());
+    }
+
+    #[test]
+    fn test_as_number_str_not_a_number() {
+        assert_eq!(None, Json::String { elem: "1" }.as_number_str());
+        assert_eq!(None, Json::Null { elem: "null" }.as_number_str());
+        assert_eq!(None, json("[]").unwrap().as_number_str());
+    }
+
+    #[test]
+    fn test_as_f64() {
+        assert_eq!(Some(42.0), Json::Number { elem: "42" }.as_f64());
+        assert_eq!(Some(-1.0), Json::Number { elem: "-1" }.as_f64());
+        assert_eq!(Some(0.64), Json::Number { elem: "0.64" }.as_f64());
+        assert_eq!(Some(1000.0), Json::Number { elem: "1e3" }.as_f64());
+        assert_eq!(Some(0.012), Json::Number { elem: "1.2E-2" }.as_f64());
+        assert_eq!(None, Json::Number { elem: "1e400" }.as_f64());
+        assert_eq!(None, Json::True { elem: "true" }.as_f64());
+    }
+
+    #[test]
+    fn test_as_i64() {
+        assert_eq!(Some(42), Json::Number { elem: "42" }.as_i64());
+        assert_eq!(Some(-1), Json::Number { elem: "-1" }.as_i64());
+        assert_eq!(None, Json::Number { elem: "0.64" }.as_i64());
+        assert_eq!(Some(1000), Json::Number { elem: "1e3" }.as_i64());
+        assert_eq!(Some(-25), Json::Number { elem: "-2.5e+1" }.as_i64());
+        assert_eq!(Some(i64::MAX), Json::Number { elem: "9223372036854775807" }.as_i64());
+        assert_eq!(None, Json::Number { elem: "1e19" }.as_i64());
+        assert_eq!(None, Json::String { elem: "42" }.as_i64());
+    }
+
+    #[test]
+    fn test_normalized_number_same_value() {
+        assert_eq!(Some("100".to_string()), normalized("1e2"));
+        assert_eq!(Some("100".to_string()), normalized("100"));
+        assert_eq!(Some("100".to_string()), normalized("100.00"));
+    }
+
+    #[test]
+    fn test_normalized_number_fractions() {
+        assert_eq!(Some("0.5".to_string()), normalized("0.50"));
+        assert_eq!(Some("-12.25".to_string()), normalized("-1225e-2"));
+        assert_eq!(Some("0.000001".to_string()), normalized("1e-6"));
+        assert_eq!(Some("0".to_string()), normalized("-0.0"));
+    }
+
+    #[test]
+    fn test_normalized_number_exponents() {
+        assert_eq!(Some("1e-7".to_string()), normalized("0.0000001"));
+        assert_eq!(Some("1.23e-18".to_string()), normalized("123e-20"));
+        assert_eq!(
+            Some("100000000000000000000".to_string()),
+            normalized("1e20")
+        );
+        assert_eq!(Some("1e+21".to_string()), normalized("1e21"));
+        assert_eq!(Some("-4.5e+30".to_string()), normalized("-45E29"));
+    }
+
+    #[test]
+    fn test_normalized_number_not_a_number() {
+        assert_eq!(None, normalized("1e400"));
+        assert_eq!(None, Json::String { elem: "1" }.normalized_number());
+    }
+}
@@ -0,0 +1,386 @@
+use super::*;
+
+/// Placeholder swapped in for redacted values.
+pub const REDACTED: &str = "***";
+
+/// Owned representation of a Json, decoupled from the lifetime of the parsed source.
+///
+/// Mirrors [`Json`] variant for variant. Its content can be modified, which is not possible on
+/// the borrowed representation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JsonOwned {
+    /// Json Objects are simply mapping of Json values.
+    ///
+    /// # NOTE
+    /// Using a vec allow to maintain order of insertion. Yields poorer lookups performance.
+    Object {
+        /// `object` representation
+        elem: Vec<(JsonOwned, JsonOwned)>,
+    },
+
+    /// Array contains consecutive Json value without a key.
+    Array {
+        /// `array` representation
+        elem: Vec<JsonOwned>,
+    },
+
+    /// `number` terminal
+    Number {
+        /// `number` representation
+        elem: String,
+    },
+    /// `string` terminal
+    String {
+        /// `string` representation
+        elem: String,
+    },
+    /// `true` terminal
+    True {
+        /// `true` representation
+        elem: String,
+    },
+    /// `false` terminal
+    False {
+        /// `false` representation
+        elem: String,
+    },
+    /// `null` terminal
+    Null {
+        /// `null` representation
+        elem: String,
+    },
+
+    /// Unparseable value, only produced when recovering from errors.
+    Error {
+        /// Slice of the source that could not be parsed
+        elem: String,
+    },
+}
+
+/// Alias of [`JsonOwned`], the owned counterpart of [`Json`].
+pub type OwnedJson = JsonOwned;
+
+impl<'a> From<&Json<'a>> for JsonOwned {
+    fn from(json: &Json<'a>) -> Self {
+        match json {
+            Json::Object { elem, .. } => Self::Object {
+                elem: elem.iter().map(|(k, v)| (k.into(), v.into())).collect(),
+            },
+            Json::Array { elem, .. } => Self::Array {
+                elem: elem.iter().map(Self::from).collect(),
+            },
+            Json::Number { elem } => Self::Number {
+                elem: elem.to_string(),
+            },
+            Json::String { elem } => Self::String {
+                elem: elem.to_string(),
+            },
+            Json::True { elem } => Self::True {
+                elem: elem.to_string(),
+            },
+            Json::False { elem } => Self::False {
+                elem: elem.to_string(),
+            },
+            Json::Null { elem } => Self::Null {
+                elem: elem.to_string(),
+            },
+            Json::Error { elem } => Self::Error {
+                elem: elem.to_string(),
+            },
+        }
+    }
+}
+
+impl<'a> Json<'a> {
+    /// Copies the Json into an [`OwnedJson`], which outlives the parsed source.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let source = String::from("[1, null]");
+    /// let owned = json(&source).unwrap().to_owned_json();
+    /// drop(source);
+    ///
+    /// assert_eq!(Some(&OwnedJson::Null { elem: "null".to_string() }), owned.pointer("/1"));
+    /// ```
+    pub fn to_owned_json(&self) -> OwnedJson {
+        JsonOwned::from(self)
+    }
+}
+
+impl JsonOwned {
+    /// Replaces the value at each JSON pointer path with the [`REDACTED`] placeholder string.
+    ///
+    /// The structure of the document is left intact. Paths that do not resolve are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let mut owned = JsonOwned::from(&json("{\"token\": \"abc\", \"id\": 7}").unwrap());
+    /// owned.redact(&["/token"]);
+    ///
+    /// let expected = JsonOwned::from(&json("{\"token\": \"***\", \"id\": 7}").unwrap());
+    /// assert_eq!(expected, owned);
+    /// ```
+    pub fn redact(&mut self, paths: &[&str]) {
+        for path in paths {
+            if let Some(node) = self.pointer_mut(path) {
+                *node = Self::String {
+                    elem: REDACTED.to_string(),
+                };
+            }
+        }
+    }
+
+    /// Truncated preview of the document.
+    ///
+    /// Keeps the first `max_children` children of every array and object, recursively. The
+    /// omitted ones are replaced by a single string marker such as `"… 95 more"`, which objects
+    /// hold under the `"…"` key.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let owned = JsonOwned::from(&json("[1, 2, 3]").unwrap());
+    /// let JsonOwned::Array { elem } = owned.sample(2) else {
+    ///     panic!("Expected an array")
+    /// };
+    /// assert_eq!(JsonOwned::String { elem: "… 1 more".to_string() }, elem[2]);
+    /// ```
+    pub fn sample(&self, max_children: usize) -> JsonOwned {
+        let marker = |omitted: usize| Self::String {
+            elem: format!("… {} more", omitted),
+        };
+
+        match self {
+            Self::Object { elem } => {
+                let mut sampled: Vec<_> = elem
+                    .iter()
+                    .take(max_children)
+                    .map(|(k, v)| (k.clone(), v.sample(max_children)))
+                    .collect();
+                if elem.len() > max_children {
+                    let key = Self::String {
+                        elem: "…".to_string(),
+                    };
+                    sampled.push((key, marker(elem.len() - max_children)));
+                }
+                Self::Object { elem: sampled }
+            }
+            Self::Array { elem } => {
+                let mut sampled: Vec<_> = elem
+                    .iter()
+                    .take(max_children)
+                    .map(|v| v.sample(max_children))
+                    .collect();
+                if elem.len() > max_children {
+                    sampled.push(marker(elem.len() - max_children));
+                }
+                Self::Array { elem: sampled }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Merges adjacent array elements for which the reducer returns a value, until none remain.
+    ///
+    /// Each merged element replaces the pair it was built from and can in turn be merged with
+    /// its neighbours. Only the elements of this array are considered, nested arrays are left
+    /// untouched, and any other variant is a no-op.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let mut owned = JsonOwned::from(&json("[\"a\", \"b\", 1, \"c\"]").unwrap());
+    /// owned.reduce_array(|a, b| match (a, b) {
+    ///     (JsonOwned::String { elem: a }, JsonOwned::String { elem: b }) => {
+    ///         Some(JsonOwned::String { elem: format!("{}{}", a, b) })
+    ///     }
+    ///     _ => None,
+    /// });
+    ///
+    /// let expected = JsonOwned::from(&json("[\"ab\", 1, \"c\"]").unwrap());
+    /// assert_eq!(expected, owned);
+    /// ```
+    pub fn reduce_array<FN>(&mut self, f: FN)
+    where
+        FN: Fn(&JsonOwned, &JsonOwned) -> Option<JsonOwned>,
+    {
+        let elem = match self {
+            Self::Array { elem } => elem,
+            _ => return,
+        };
+
+        let mut idx = 0;
+        while idx + 1 < elem.len() {
+            match f(&elem[idx], &elem[idx + 1]) {
+                Some(merged) => {
+                    elem[idx] = merged;
+                    elem.remove(idx + 1);
+                    // The merged element may now merge with the previous one
+                    idx = idx.saturating_sub(1);
+                }
+                None => idx += 1,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owned(source: &str) -> JsonOwned {
+        JsonOwned::from(&json(source).unwrap())
+    }
+
+    #[test]
+    fn test_from_json() {
+        assert_eq!(
+            JsonOwned::Array {
+                elem: vec!(
+                    JsonOwned::Number { elem: "1".into() },
+                    JsonOwned::Object {
+                        elem: vec!((
+                            JsonOwned::String { elem: "a".into() },
+                            JsonOwned::Null {
+                                elem: "null".into()
+                            }
+                        ))
+                    }
+                )
+            },
+            owned("[1, {\"a\": null}]")
+        );
+    }
+
+    #[test]
+    fn test_to_owned_json_outlives_source() {
+        let owned = {
+            let source = String::from("{\"a\": [\"x\", 2]}");
+            json(&source).unwrap().to_owned_json()
+        };
+
+        assert_eq!(
+            Some(&OwnedJson::String { elem: "x".into() }),
+            owned.pointer("/a/0")
+        );
+        assert_eq!(
+            Some(&OwnedJson::Number { elem: "2".into() }),
+            owned.pointer("/a/1")
+        );
+    }
+
+    #[test]
+    fn test_redact() {
+        let mut document = owned(
+            "{\"user\": \"bob\", \"password\": \"hunter2\", \
+            \"nested\": {\"secret\": 42, \"visible\": [true]}}",
+        );
+        document.redact(&["/password", "/nested/secret", "/missing", "/user/0"]);
+
+        assert_eq!(
+            owned(
+                "{\"user\": \"bob\", \"password\": \"***\", \
+                \"nested\": {\"secret\": \"***\", \"visible\": [true]}}"
+            ),
+            document
+        );
+    }
+
+    #[test]
+    fn test_redact_array_element() {
+        let mut document = owned("{\"keys\": [\"a\", \"b\", \"c\"]}");
+        document.redact(&["/keys/1"]);
+        assert_eq!(owned("{\"keys\": [\"a\", \"***\", \"c\"]}"), document);
+    }
+
+    #[test]
+    fn test_reduce_array_sums_numbers() {
+        let sum = |a: &JsonOwned, b: &JsonOwned| match (a, b) {
+            (JsonOwned::Number { elem: a }, JsonOwned::Number { elem: b }) => {
+                let total = a.parse::<i64>().ok()? + b.parse::<i64>().ok()?;
+                Some(JsonOwned::Number {
+                    elem: total.to_string(),
+                })
+            }
+            _ => None,
+        };
+
+        let mut document = owned("[1, 2, 3, \"a\", 4, null, 5, 6, [7, 8]]");
+        document.reduce_array(sum);
+        assert_eq!(owned("[6, \"a\", 4, null, 11, [7, 8]]"), document);
+
+        let mut document = owned("{\"a\": 1}");
+        document.reduce_array(sum);
+        assert_eq!(owned("{\"a\": 1}"), document);
+    }
+
+    #[test]
+    fn test_reduce_array_merges_backward() {
+        // "a" only merges with "bc", which is built after "a" was first visited
+        let mut document = owned("[\"a\", \"b\", \"c\"]");
+        document.reduce_array(|a, b| match (a, b) {
+            (JsonOwned::String { elem: a }, JsonOwned::String { elem: b })
+                if (a == "b" && b == "c") || (a == "a" && b == "bc") =>
+            {
+                Some(JsonOwned::String {
+                    elem: format!("{}{}", a, b),
+                })
+            }
+            _ => None,
+        });
+        assert_eq!(owned("[\"abc\"]"), document);
+    }
+
+    fn marker(omitted: usize) -> JsonOwned {
+        JsonOwned::String {
+            elem: format!("… {} more", omitted),
+        }
+    }
+
+    fn with_marker(document: &str, omitted: usize) -> JsonOwned {
+        let mut document = owned(document);
+        match &mut document {
+            JsonOwned::Array { elem } => elem.push(marker(omitted)),
+            JsonOwned::Object { elem } => elem.push((
+                JsonOwned::String {
+                    elem: "…".to_string(),
+                },
+                marker(omitted),
+            )),
+            _ => panic!("Expected a container"),
+        }
+        document
+    }
+
+    #[test]
+    fn test_sample_large_array() {
+        let numbers: Vec<String> = (0..100).map(|n| n.to_string()).collect();
+        let document = owned(&format!("[{}]", numbers.join(", ")));
+
+        assert_eq!(with_marker("[0, 1, 2, 3, 4]", 95), document.sample(5));
+        assert_eq!(document, document.sample(100));
+    }
+
+    #[test]
+    fn test_sample_nested() {
+        let document = owned("{\"a\": {\"x\": [1, 2], \"y\": 3}, \"b\": 4, \"c\": 5}");
+
+        let mut inner = with_marker("{\"x\": null}", 1);
+        if let JsonOwned::Object { elem } = &mut inner {
+            elem[0].1 = with_marker("[1]", 1);
+        }
+        let mut expected = with_marker("{\"a\": null}", 2);
+        if let JsonOwned::Object { elem } = &mut expected {
+            elem[0].1 = inner;
+        }
+
+        assert_eq!(expected, document.sample(1));
+        assert_eq!(with_marker("[]", 1), owned("[[]]").sample(0));
+    }
+}
@@ -1,10 +1,45 @@
 use super::*;
 use crate::parsers::*;
 use crate::regexes::*;
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+thread_local! {
+    /// Remainder length and message of the furthest failure recorded during the current parse.
+    ///
+    /// Most failures are backtracked over by `or` and the comma lists, the furthest one is where
+    /// an invalid document stopped making progress. Recording it on the side keeps the `String`
+    /// errors of the parsers untouched. At equal offsets the first failure is kept, as it is the
+    /// most specific one.
+    static FURTHEST: RefCell<Option<(usize, Cow<'static, str>)>> = const { RefCell::new(None) };
+}
+
+/// Records a failure on the given input, unless one was already recorded that far.
+fn record_failure(input: &str, message: Cow<'static, str>) {
+    FURTHEST.with_borrow_mut(|furthest| match furthest {
+        Some((len, _)) if *len <= input.len() => (),
+        _ => *furthest = Some((input.len(), message)),
+    });
+}
+
+/// Records the failures of a parser under the given message, passing its errors through.
+const fn tracked<'a, P, O>(
+    parser: P,
+    message: &'static str,
+) -> impl Parser<&'a str, &'a str, O, String>
+where
+    P: Parser<&'a str, &'a str, O, String>,
+{
+    move |input: &'a str| {
+        parser
+            .parse(input)
+            .inspect_err(|_| record_failure(input, Cow::Borrowed(message)))
+    }
+}
 
 /// Parse a slice representing Json into a `JsonAst`.
 ///
-/// Fails if the json is not valid
+/// Fails if the json is not valid, or if anything but whitespace follows it.
 ///
 /// # Example
 /// ```
@@ -17,82 +52,386 @@ use crate::regexes::*;
 ///             Json::String { elem: "foo" },
 ///             Json::True { elem: "true" },
 ///             Json::Object {
-///                 elem: vec!((Json::String { elem: "name" }, Json::String { elem: "bob" }))
+///                 elem: vec!((Json::String { elem: "name" }, Json::String { elem: "bob" })),
+///                 text: Verbatim::default(),
 ///             }
-///         )
+///         ),
+///         text: Verbatim::default(),
 ///     }),
 ///     json("[\"bar\", \"foo\", true, {\"name\": \"bob\"}]")
 ///  );
 /// ```
-pub fn json(source: &str) -> Result<Json, String> {
-    or(object, array).parse(source).map(|(_, json)| json)
+pub fn json(source: &str) -> Result<Json<'_>, String> {
+    json_with(source, &JsonOptions::default())
 }
 
-/// Parse any terminal value. Terminal values are values that are not recursive json data.
-pub const fn value<'a>() -> impl Parser<&'a str, &'a str, Json<'a>, String> {
-    // Use fastest failing derivation first
-    let parser = or(object, array);
-    let parser = or(parser, terminal_value());
-    parser
+/// Same as [`json`], but failures are located in the source.
+///
+/// The error implements `std::error::Error`, which lets it bubble up with `?` in applications.
+///
+/// # Example
+/// ```
+///  use parser_combinator::json::*;
+///
+///  let err = json_typed("{\"a\": [1, nope]}").unwrap_err();
+///  assert_eq!(10, err.offset);
+///  assert!(json_typed("{\"a\": [1]}").is_ok());
+/// ```
+pub fn json_typed(source: &str) -> Result<Json<'_>, ParseError> {
+    document(source, &JsonOptions::default())
+}
+
+/// Options enabling the tolerant parsing modes of [`json_with`].
+///
+/// The default options are strict and match the behavior of [`json`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    /// Values that cannot be parsed become a [`Json::Error`] node instead of failing the parse.
+    pub recover_errors: bool,
+    /// Objects holding the same key more than once fail the parse.
+    pub reject_duplicate_keys: bool,
+    /// Maximum number of nested arrays and objects, the top level one included.
+    ///
+    /// Deeper documents fail the parse instead of overflowing the stack. Unlimited if None.
+    pub max_depth: Option<usize>,
+    /// Arrays and objects may end with a single trailing comma, as in `[1, 2,]`.
+    pub allow_trailing_commas: bool,
+    /// `//` line comments and `/* */` block comments are skipped wherever whitespace is allowed,
+    /// as well as before the top level value.
+    pub allow_comments: bool,
+}
+
+/// Parse a slice representing Json into a `JsonAst`, tolerating comments and trailing commas.
+///
+/// This is the subset of JSON5 commonly found in configuration files, any other JSON5
+/// extension is still rejected.
+///
+/// # Example
+/// ```
+///  use parser_combinator::json::*;
+///
+///  let source = "// Settings\n{\n  \"retries\": 3, /* per host */\n}";
+///  assert_eq!(json("{\"retries\": 3}"), json_lenient(source));
+///  assert!(json(source).is_err());
+/// ```
+pub fn json_lenient(source: &str) -> Result<Json<'_>, String> {
+    let options = JsonOptions {
+        allow_trailing_commas: true,
+        allow_comments: true,
+        ..JsonOptions::default()
+    };
+    json_with(source, &options)
+}
+
+/// Parse a slice representing Json into a `JsonAst`, according to the given options.
+///
+/// # Example
+/// ```
+///  use parser_combinator::json::*;
+///
+///  let options = JsonOptions { recover_errors: true, ..JsonOptions::default() };
+///  assert_eq!(
+///     Ok(Json::Array {
+///         elem: vec!(Json::Error { elem: "nope" }, Json::True { elem: "true" }),
+///         text: Verbatim::default(),
+///     }),
+///     json_with("[nope, true]", &options)
+///  );
+///  assert!(json("[nope, true]").is_err());
+/// ```
+pub fn json_with<'a>(source: &'a str, options: &JsonOptions) -> Result<Json<'a>, String> {
+    document(source, options).map_err(String::from)
+}
+
+/// Parses a whole document, locating the failure in the source when it is invalid.
+fn document<'a>(source: &'a str, options: &JsonOptions) -> Result<Json<'a>, ParseError> {
+    document_with(source, options, false)
+}
+
+/// Same as `document`, but the elements of arrays and objects are dropped as soon as they are
+/// parsed when `discard` is set, leaving them empty.
+pub(crate) fn document_with<'a>(
+    source: &'a str,
+    options: &JsonOptions,
+    discard: bool,
+) -> Result<Json<'a>, ParseError> {
+    let parser = or(
+        |input: &'a str| object_with(input, options, 1, discard),
+        |input: &'a str| array_with(input, options, 1, discard),
+    );
+    let parser = tracked(parser, "Expected an object or an array");
+    let located = |message: String| {
+        let (len, message) = FURTHEST
+            .take()
+            .unwrap_or((source.len(), Cow::Owned(message)));
+        ParseError {
+            message: message.into_owned(),
+            offset: source.len() - len.min(source.len()),
+            expected: Vec::new(),
+        }
+    };
+
+    // Direct uses of `value`, `object` or `array` may have left failures behind
+    FURTHEST.take();
+    let start = match options.allow_comments {
+        true => blank(options).parse(source).map_err(located)?.0,
+        false => source,
+    };
+    match parser.parse(start).map_err(located)? {
+        ("", json) if options.reject_duplicate_keys => match duplicate_key(&json) {
+            Some(key) => Err(ParseError::at(format!("Duplicate key '{}'", key), source, key)),
+            None => Ok(json),
+        },
+        ("", json) => Ok(json),
+        (rem, _) => {
+            let snippet: String = rem.chars().take(16).collect();
+            let message = format!("Unexpected trailing characters '{}'", snippet);
+            Err(ParseError::at(message, source, rem))
+        }
+    }
+}
+
+/// Finds the first key, in document order, appearing twice in the same object.
+///
+/// # Note
+/// Done once the document is parsed, as `or` would discard the error raised by the object parser.
+fn duplicate_key<'a>(json: &Json<'a>) -> Option<&'a str> {
+    match json {
+        Json::Object { elem, .. } => elem.iter().enumerate().find_map(|(idx, (key, value))| {
+            let seen = elem[..idx].iter().any(|(k, _)| k == key);
+            match key {
+                Json::String { elem } if seen => Some(*elem),
+                _ => duplicate_key(value),
+            }
+        }),
+        Json::Array { elem, .. } => elem.iter().find_map(duplicate_key),
+        _ => None,
+    }
+}
+
+/// Skips whitespace, and comments if the options allow them. Always succeeds.
+const fn blank<'a, 'o>(
+    options: &'o JsonOptions,
+) -> impl Parser<&'a str, &'a str, (), String> + use<'a, 'o> {
+    move |input: &'a str| {
+        let (mut rem, _) = maybe(whitespace).parse(input)?;
+        if options.allow_comments {
+            while let Ok((comment_rem, _)) = comment.parse(rem) {
+                rem = maybe(whitespace).parse(comment_rem)?.0;
+            }
+        }
+        Ok((rem, ()))
+    }
+}
+
+/// Parse any Json value, nested in `depth` arrays and objects.
+pub const fn value<'a, 'o>(
+    options: &'o JsonOptions,
+    depth: usize,
+) -> impl Parser<&'a str, &'a str, Json<'a>, String> + use<'a, 'o> {
+    value_with(options, depth, false)
+}
+
+/// Same as [`value`], dropping the elements of nested arrays and objects if `discard` is set.
+const fn value_with<'a, 'o>(
+    options: &'o JsonOptions,
+    depth: usize,
+    discard: bool,
+) -> impl Parser<&'a str, &'a str, Json<'a>, String> + use<'a, 'o> {
+    move |input: &'a str| {
+        // Dispatch on the first byte, a terminal must not go through the depth check of the
+        // containers
+        let parser = |input: &'a str| match input.as_bytes().first() {
+            Some(b'{') => object_with(input, options, depth + 1, discard),
+            Some(b'[') => array_with(input, options, depth + 1, discard),
+            _ => terminal_value().parse(input),
+        };
+
+        let (input, _) = blank(options).parse(input)?;
+        let parser = tracked(parser, "Expected a value");
+        let (rem, value) = if options.recover_errors {
+            recover_value(parser).parse(input)?
+        } else {
+            parser.parse(input)?
+        };
+        let (rem, _) = blank(options).parse(rem)?;
+        Ok((rem, value))
+    }
+}
+
+/// Fails if a container starting the input at the given depth exceeds the maximum depth of the
+/// options.
+fn check_depth(input: &str, options: &JsonOptions, depth: usize) -> Result<(), String> {
+    match options.max_depth {
+        Some(max_depth) if depth > max_depth => {
+            let message = format!("Exceeded the maximum depth of {}", max_depth);
+            record_failure(input, Cow::Owned(message.clone()));
+            Err(message)
+        }
+        _ => Ok(()),
+    }
 }
 
-/// Parse a Json object.
+/// Parse a Json object, `depth` being its own nesting level.
 ///
 /// A Json object is a series of 'key: value' pair encased in '{ }'.
 ///
 /// # Note
 /// Object is a concrete parser instead of a combined parser in order
 /// to break type recursion.
-pub fn object<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, Json<'b>), String> {
-    let middle_pair = left(key_value_pair(), literal(","));
-    let middle_pair = left(middle_pair, maybe(whitespace));
-
-    let content = while_(or(middle_pair, key_value_pair()));
-    let content = right(maybe(whitespace), content);
+pub fn object<'b, 'a: 'b>(
+    input: &'a str,
+    options: &JsonOptions,
+    depth: usize,
+) -> Result<(&'b str, Json<'b>), String> {
+    object_with(input, options, depth, false)
+}
 
-    let parser = middle(literal("{"), content, literal("}"));
-    let parser = left(parser, maybe(whitespace));
+/// Same as [`object`], dropping the pairs as soon as they are parsed if `discard` is set.
+fn object_with<'b, 'a: 'b>(
+    input: &'a str,
+    options: &JsonOptions,
+    depth: usize,
+    discard: bool,
+) -> Result<(&'b str, Json<'b>), String> {
+    check_depth(input, options, depth)?;
+    let pairs = comma_fold(
+        key_value_pair(options, depth, discard),
+        options.allow_trailing_commas,
+        Vec::new,
+        move |pairs, pair| keep_unless(discard, pairs, pair),
+    );
+    let parser = middle(
+        left(literal("{"), blank(options)),
+        pairs,
+        right(blank(options), tracked(literal("}"), "Expected ',' or '}'")),
+    );
+    let (rem, elem) = parser.parse(input)?;
+    let text = Verbatim(Some(&input[..input.len() - rem.len()]));
+    let (rem, _) = blank(options).parse(rem)?;
 
-    map(parser, |elem| Json::Object { elem }).parse(input)
+    Ok((rem, Json::Object { elem, text }))
 }
 
-/// Parse a Json Array.
+/// Parse a Json Array, `depth` being its own nesting level.
 ///
 /// A Json array is a series of 'value' pair encased in '[ ]'.
 ///
 /// # Note
 /// Array is a concrete parser instead of a combined parser in order to break type recursion.
-pub fn array<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, Json<'b>), String> {
-    let last_value = right(maybe(whitespace), value());
-    let last_value = left(last_value, maybe(whitespace));
-
-    let middle_value = right(maybe(whitespace), value());
-    let middle_value = left(middle_value, literal(","));
-    let middle_value = left(middle_value, maybe(whitespace));
+pub fn array<'b, 'a: 'b>(
+    input: &'a str,
+    options: &JsonOptions,
+    depth: usize,
+) -> Result<(&'b str, Json<'b>), String> {
+    array_with(input, options, depth, false)
+}
 
-    let array_value = or(middle_value, last_value);
-    let array_content = while_(array_value);
+/// Same as [`array`], dropping the values as soon as they are parsed if `discard` is set.
+fn array_with<'b, 'a: 'b>(
+    input: &'a str,
+    options: &JsonOptions,
+    depth: usize,
+    discard: bool,
+) -> Result<(&'b str, Json<'b>), String> {
+    check_depth(input, options, depth)?;
+    let values = comma_fold(
+        value_with(options, depth, discard),
+        options.allow_trailing_commas,
+        Vec::new,
+        move |values, value| keep_unless(discard, values, value),
+    );
+    let parser = middle(
+        left(literal("["), blank(options)),
+        values,
+        right(blank(options), tracked(literal("]"), "Expected ',' or ']'")),
+    );
+    let (rem, elem) = parser.parse(input)?;
+    let text = Verbatim(Some(&input[..input.len() - rem.len()]));
+    let (rem, _) = blank(options).parse(rem)?;
 
-    let parser = middle(literal("["), array_content, literal("]"));
-    let parser = left(parser, maybe(whitespace));
+    Ok((rem, Json::Array { elem, text }))
+}
 
-    map(parser, |elem| Json::Array { elem }).parse(input)
+/// Pushes an element of an array or an object, unless elements are discarded.
+///
+/// An empty vector does not allocate, so discarding elements builds none.
+fn keep_unless<T>(discard: bool, mut elems: Vec<T>, elem: T) -> Vec<T> {
+    if !discard {
+        elems.push(elem);
+    }
+    elems
 }
 
 /// Parse a Json object key value pair
 ///
 /// # Note
 /// Defined as a private top level function to avoid using moved value in the object parser.
-const fn key_value_pair<'a>() -> impl Parser<&'a str, &'a str, (Json<'a>, Json<'a>), String> {
-    let key = left(string(), maybe(whitespace));
-    let key = left(key, literal(":"));
-    let key = left(key, maybe(whitespace));
+const fn key_value_pair<'a, 'o>(
+    options: &'o JsonOptions,
+    depth: usize,
+    discard: bool,
+) -> impl Parser<&'a str, &'a str, (Json<'a>, Json<'a>), String> + use<'a, 'o> {
+    // Comments may sit between the separating comma and the key
+    let key = middle(
+        blank(options),
+        tracked(string(), "Expected a string key"),
+        blank(options),
+    );
+    let key = left(key, tracked(literal(":"), "Expected ':'"));
+    let key = left(key, blank(options));
 
-    let parser = and(key, value());
+    let parser = and(key, value_with(options, depth, discard));
 
     parser
 }
 
+/// Turns the failure of a value parser into a [`Json::Error`] node.
+///
+/// The node captures the unparseable slice, up to the next `,`, `]` or `}` outside of a quoted
+/// string, so that the enclosing array or object can carry on.
+///
+/// # Result Conditions
+/// Fails only if the parser fails and there is nothing to capture before the next delimiter.
+pub const fn recover_value<'a, P>(parser: P) -> impl Parser<&'a str, &'a str, Json<'a>, String>
+where
+    P: Parser<&'a str, &'a str, Json<'a>, String>,
+{
+    move |input: &'a str| match parser.parse(input) {
+        Ok(r) => Ok(r),
+        Err(err) => {
+            let end = recovery_end(input);
+            let elem = input[..end].trim_end();
+
+            // An empty capture would let repeating parsers loop without consuming anything
+            if elem.is_empty() {
+                return Err(err);
+            }
+
+            let (rem, _) = maybe(whitespace).parse(&input[elem.len()..])?;
+            Ok((rem, Json::Error { elem }))
+        }
+    }
+}
+
+/// Byte offset of the first delimiter of the input that is not inside a quoted string.
+///
+/// Returns the input length if there is none, an unterminated string running to the end.
+fn recovery_end(input: &str) -> usize {
+    let mut rem = input;
+    while let Some(c) = rem.chars().next() {
+        match c {
+            ',' | ']' | '}' => break,
+            '"' => match quoted_string('"', '\\').parse(rem) {
+                Ok((after, _)) => rem = after,
+                Err(_) => return input.len(),
+            },
+            _ => rem = &rem[c.len_utf8()..],
+        }
+    }
+    input.len() - rem.len()
+}
+
 /// Parse any terminal value.
 ///
 /// Terminal values are values that are not recursive json data.
@@ -104,9 +443,7 @@ pub const fn terminal_value<'a>() -> impl Parser<&'a str, &'a str, Json<'a>, Str
     let parser = or(parser, null_());
 
     // Consume whitespaces after all terminal values
-    let parser = left(parser, maybe(whitespace));
-
-    parser
+    token(parser)
 }
 
 /// Parses a `true` terminal.
@@ -149,17 +486,29 @@ pub const fn string<'a>() -> impl Parser<&'a str, &'a str, Json<'a>, String> {
 pub fn number_raw<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, &'b str), String> {
     // Note: Because it is not the point of the project, the regex used is a shameless steal from:
     //   https://stackoverflow.com/questions/13340717/json-numbers-regular-expression
-    matching(&JSON_NUMBER_REGEX).parse(input)
+    let (rem, number) = matching::<String>(&JSON_NUMBER_REGEX).parse(input)?;
+
+    // The regex only matches a prefix, `01` or `1.` would otherwise leave a stray remainder
+    match rem.chars().next() {
+        Some(c) if c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E') => {
+            let end = input
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-')))
+                .unwrap_or(input.len());
+            Err(format!("Malformed number '{}'", &input[..end]))
+        }
+        _ => Ok((rem, number)),
+    }
 }
 
 /// Parse all json string char
-
+///
 /// # Note
 /// This is a concrete parser, it is an indirection to be able to use a non-const value in const
 /// functions.
 pub fn string_content<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, &'b str), String> {
     // String gets a bit annoying as we may have escape character. A hand written parser
     // is better suited in this case.
+    // `idx` is a byte offset, characters may span several bytes
     let mut chars = input.chars().peekable();
     let mut idx = 0;
     loop {
@@ -167,12 +516,10 @@ pub fn string_content<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, &'b str),
             // Consume 2 chars (escape plus next)
             Some('\\') => {
                 chars.next();
-                match chars.peek() {
-                    Some(c) => chars.next(),
+                match chars.next() {
+                    Some(c) => idx += 1 + c.len_utf8(),
                     _ => return Err("Unexpected end of stream here".into()),
                 };
-
-                idx += 2;
             }
 
             // Probably hit the end of the string
@@ -184,17 +531,13 @@ pub fn string_content<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, &'b str),
             }
 
             // Anything else, we consume
-            _ => {
+            Some(c) => {
+                idx += c.len_utf8();
                 chars.next();
-                idx += 1;
             }
         }
     }
 
-    if input.len() < idx {
-        return Err("Derivation would exceed end of string".into());
-    }
-
     Ok((&input[idx..], &input[0..idx]))
 }
 
@@ -245,20 +588,37 @@ fn terminal_json_value_demo() {
     );
 }
 
+/// The `string` terminal expressed with the generic `quoted_string` parser
+#[test]
+fn string_as_quoted_string() {
+    let quoted = map(quoted_string('"', '\\'), |elem| Json::String { elem });
+
+    for input in ["\"foo\"", "\"\\\\\"", "\"\\CODE\"", "\"two words\"", "\"\\\"\" rest"] {
+        assert_eq!(string().parse(input), quoted.parse(input));
+    }
+}
+
 #[test]
 fn test_object() {}
 
 #[test]
 fn json_demo_1() {
     assert!(json("{}").is_ok());
-    assert_eq!(Ok(Json::Object { elem: vec!() }), json("{}"));
+    assert_eq!(
+        Ok(Json::Object {
+            elem: vec!(),
+            text: Verbatim::default()
+        }),
+        json("{}")
+    );
 }
 
 #[test]
 fn json_demo_2() {
     assert_eq!(
         Ok(Json::Object {
-            elem: vec!((Json::String { elem: "foo" }, Json::String { elem: "bar" }))
+            elem: vec!((Json::String { elem: "foo" }, Json::String { elem: "bar" })),
+            text: Verbatim::default(),
         }),
         json(
             "{ \
@@ -278,7 +638,8 @@ fn json_demo_3() {
                     Json::String { elem: "2nd_key" },
                     Json::True { elem: "true" }
                 )
-            )
+            ),
+            text: Verbatim::default(),
         }),
         json(
             "{ \
@@ -293,7 +654,8 @@ fn json_demo_3() {
 fn json_demo_4() {
     assert_eq!(
         Ok(Json::Array {
-            elem: vec!(Json::String { elem: "bar" })
+            elem: vec!(Json::String { elem: "bar" }),
+            text: Verbatim::default(),
         }),
         json("[ \"bar\" ]")
     );
@@ -307,7 +669,8 @@ fn json_demo_5() {
                 Json::String { elem: "bar" },
                 Json::String { elem: "foo" },
                 Json::True { elem: "true" }
-            )
+            ),
+            text: Verbatim::default(),
         }),
         json("[\"bar\", \"foo\", true]")
     );
@@ -322,9 +685,11 @@ fn json_demo_6() {
                 Json::String { elem: "foo" },
                 Json::True { elem: "true" },
                 Json::Object {
-                    elem: vec!((Json::String { elem: "name" }, Json::String { elem: "bob" }))
+                    elem: vec!((Json::String { elem: "name" }, Json::String { elem: "bob" })),
+                    text: Verbatim::default(),
                 }
-            )
+            ),
+            text: Verbatim::default(),
         }),
         json("[\"bar\", \"foo\", true, {\"name\": \"bob\"}]")
     );
@@ -345,11 +710,14 @@ fn json_demo_7() {
                             elem: vec!((
                                 Json::String { elem: "name" },
                                 Json::String { elem: "bob" }
-                            ))
+                            )),
+                            text: Verbatim::default(),
                         }
-                    )
+                    ),
+                    text: Verbatim::default(),
                 }
-            ))
+            )),
+            text: Verbatim::default(),
         }),
         json(
             "{ \
@@ -363,7 +731,429 @@ fn json_demo_7() {
     );
 }
 
+#[test]
+fn json_rejects_trailing_comma() {
+    assert!(json("[\"bar\",]").is_err());
+    assert!(json("[\"bar\", \"foo\" , ]").is_err());
+    assert!(json("{\"foo\": \"bar\",}").is_err());
+    assert!(json("[,]").is_err());
+}
+
+#[test]
+fn json_rejects_missing_comma() {
+    assert!(json("[\"bar\" \"foo\"]").is_err());
+    assert!(json("{\"foo\": 1 \"bar\": 2}").is_err());
+}
+
+#[test]
+fn json_rejects_trailing_input() {
+    // Used to be silently accepted, the remainder being discarded
+    assert_eq!(
+        Err("Unexpected trailing characters 'trailing'".to_string()),
+        json("[] trailing")
+    );
+    assert!(json("{} garbage").is_err());
+    assert!(json("[1]]").is_err());
+
+    assert!(json("[1] \n").is_ok());
+}
+
+#[test]
+fn number_raw_accepts_valid_numbers() {
+    assert_eq!(Ok(("", "0")), number_raw("0"));
+    assert_eq!(Ok(("", "-0")), number_raw("-0"));
+    assert_eq!(Ok(("", "0.5")), number_raw("0.5"));
+    assert_eq!(Ok(("", "1e10")), number_raw("1e10"));
+    assert_eq!(Ok(("", "-12.5E-3")), number_raw("-12.5E-3"));
+    assert_eq!(Ok((", 2]", "10")), number_raw("10, 2]"));
+    assert_eq!(Ok(("}", "0")), number_raw("0}"));
+}
+
+#[test]
+fn number_raw_rejects_malformed_numbers() {
+    assert_eq!(Err("Malformed number '01'".to_string()), number_raw("01"));
+    assert_eq!(Err("Malformed number '0123'".to_string()), number_raw("0123, 4"));
+    assert_eq!(Err("Malformed number '1.'".to_string()), number_raw("1."));
+    assert_eq!(Err("Malformed number '1e'".to_string()), number_raw("1e"));
+    assert_eq!(Err("Malformed number '-01.5'".to_string()), number_raw("-01.5"));
+    assert!(number_raw("-").is_err());
+    assert!(number_raw(".5").is_err());
+    assert!(number_raw("+1").is_err());
+    assert!(json("[0123]").is_err());
+}
+
+#[test]
+fn terminal_expectations() {
+    let parser = or_expecting(expecting(number(), "number"), expecting(true_(), "true"));
+    let err = parser.parse("xyz").unwrap_err();
+    assert_eq!(vec!("number", "true"), err.expected);
+    assert_eq!("Expected one of: number, true", err.message);
+}
+
+#[test]
+fn json_unicode_strings() {
+    let source = "[\"café\", \"🦀 crab\", \"\\é\"]";
+    assert_eq!(
+        Ok(Json::Array {
+            elem: vec!(
+                Json::String { elem: "café" },
+                Json::String { elem: "🦀 crab" },
+                Json::String { elem: "\\é" },
+            ),
+            text: Verbatim::default(),
+        }),
+        json(source)
+    );
+    let rendered = minify(source).unwrap();
+    assert_eq!("[\"café\",\"🦀 crab\",\"\\é\"]", rendered);
+    assert_eq!(json(source), json(&rendered));
+}
+
+#[test]
+fn json_keywords_are_case_sensitive() {
+    assert!(true_().parse("TRUE").is_err());
+    assert!(null_().parse("Null").is_err());
+    assert!(json("[TRUE]").is_err());
+}
+
+#[test]
+fn json_rejects_trailing_value() {
+    assert_eq!(
+        Err("Unexpected trailing characters '{}'".to_string()),
+        json("{} {}")
+    );
+    assert_eq!(
+        Err("Unexpected trailing characters 'x'".to_string()),
+        json("[]x")
+    );
+    assert_eq!(
+        Ok(Json::Object {
+            elem: vec!(),
+            text: Verbatim::default()
+        }),
+        json("{}   ")
+    );
+}
+
+#[test]
+fn json_typed_is_an_error() {
+    fn first_name(source: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let parsed = json_typed(source)?;
+        Ok(parsed.pointer("/name").ok_or("Missing name")?.to_string())
+    }
+
+    assert_eq!("\"bob\"", first_name("{\"name\": \"bob\"}").unwrap());
+    assert_eq!("Missing name", first_name("{}").unwrap_err().to_string());
+
+    let err = first_name("{\"name\": bob}").unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!(9, err.offset);
+}
+
+#[test]
+fn json_typed_display() {
+    let display = |source| json_typed(source).unwrap_err().to_string();
+    assert_eq!("Unexpected trailing characters 'x' at byte 7", display("[1, 2] x"));
+    assert_eq!("Expected an object or an array at byte 0", display("x"));
+    assert_eq!("Expected a value at byte 4", display("[1, x]"));
+    assert_eq!("Expected a value at byte 1", display("[\"abc"));
+    assert_eq!("Expected ',' or ']' at byte 3", display("[1 2]"));
+
+    let options = JsonOptions {
+        reject_duplicate_keys: true,
+        ..JsonOptions::default()
+    };
+    let err = document("{\"a\": 1, \"a\": 2}", &options).unwrap_err();
+    assert_eq!("Duplicate key 'a' at byte 10", err.to_string());
+}
+
+#[test]
+fn json_typed_display_objects() {
+    let display = |source| json_typed(source).unwrap_err().to_string();
+    assert_eq!("Expected a string key at byte 9", display("{\"a\": 1, 2}"));
+    assert_eq!("Expected a value at byte 6", display("{\"a\": nope}"));
+    assert_eq!("Expected ':' at byte 5", display("{\"a\" 1}"));
+    assert_eq!("Expected ',' or '}' at byte 8", display("{\"a\": 1 \"b\": 2}"));
+    assert_eq!("Expected ',' or '}' at byte 14", display("{\"a\": [1, 2]  "));
+    assert_eq!("Expected a value at byte 16", display("{\"a\": {\"b\": [1, ]}}"));
+
+    let options = JsonOptions {
+        max_depth: Some(2),
+        ..JsonOptions::default()
+    };
+    let err = document("{\"a\": {\"b\": {}}}", &options).unwrap_err();
+    assert_eq!("Exceeded the maximum depth of 2 at byte 12", err.to_string());
+    let err = document("{\"a\": {\"b\": nope}}", &options).unwrap_err();
+    assert_eq!("Expected a value at byte 12", err.to_string());
+}
+
+#[test]
+fn json_typed_resets_failures() {
+    let options = JsonOptions::default();
+    assert!(value(&options, 0).parse("[1, 2, 3, x]").is_err());
+    assert!(object("{\"a\": 1, \"b\": 2, \"c\": x}", &options, 1).is_err());
+    assert_eq!(
+        "Expected a value at byte 1",
+        json_typed("[x]").unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn json_typed_offset() {
+    let offset = |source| json_typed(source).unwrap_err().offset;
+    assert_eq!(0, offset(""));
+    assert_eq!(0, offset("x"));
+    assert_eq!(4, offset("[1, x]"));
+    assert_eq!(3, offset("[1 2]"));
+    assert_eq!(9, offset("{\"a\": 1, 2}"));
+    assert_eq!(14, offset("{\"a\": [1, 2]  "));
+}
+
+#[test]
+fn json_duplicate_keys() {
+    let options = JsonOptions {
+        reject_duplicate_keys: true,
+        ..JsonOptions::default()
+    };
+
+    assert_eq!(
+        Err("Duplicate key 'a'".to_string()),
+        json_with("{\"a\": 1, \"a\": 2}", &options)
+    );
+    assert_eq!(
+        Err("Duplicate key 'b'".to_string()),
+        json_with("[{\"x\": {\"b\": 1, \"b\": 2}, \"a\": 1, \"a\": 2}]", &options)
+    );
+    assert!(json("{\"a\": 1, \"a\": 2}").is_ok());
+}
+
+#[test]
+fn json_unique_keys() {
+    let options = JsonOptions {
+        reject_duplicate_keys: true,
+        ..JsonOptions::default()
+    };
+
+    let source = "{\"a\": {\"a\": 1}, \"b\": [{\"a\": 2}, {\"a\": 3}]}";
+    assert_eq!(json(source), json_with(source, &options));
+    assert!(json_with(include_str!("sample.json"), &options).is_ok());
+}
+
+#[test]
+fn json_max_depth() {
+    let options = JsonOptions {
+        max_depth: Some(3),
+        ..JsonOptions::default()
+    };
+
+    assert!(json_with("[{\"a\": [1]}, []]", &options).is_ok());
+    assert!(json_with("[{\"a\": [[1]]}]", &options).is_err());
+    assert!(json_with("{\"a\": {\"b\": {\"c\": {}}}}", &options).is_err());
+}
+
+#[test]
+fn json_max_depth_deeply_nested() {
+    let options = JsonOptions {
+        max_depth: Some(128),
+        ..JsonOptions::default()
+    };
+
+    let source = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+    assert!(json_with(&source, &options).is_err());
+}
+
+#[test]
+fn json_allow_trailing_commas() {
+    let options = JsonOptions {
+        allow_trailing_commas: true,
+        ..JsonOptions::default()
+    };
+
+    assert_eq!(json("[1,2]"), json_with("[1,2,]", &options));
+    assert_eq!(json("{\"a\":1}"), json_with("{\"a\":1,}", &options));
+    assert_eq!(json("[[], {}]"), json_with("[[] , {} , ]", &options));
+    assert!(json("[1,2,]").is_err());
+}
+
+#[test]
+fn json_allow_trailing_commas_single() {
+    let options = JsonOptions {
+        allow_trailing_commas: true,
+        ..JsonOptions::default()
+    };
+
+    assert!(json_with("[1,,2]", &options).is_err());
+    assert!(json_with("[1,,]", &options).is_err());
+    assert!(json_with("[,]", &options).is_err());
+    assert!(json_with("{,}", &options).is_err());
+}
+
+#[test]
+fn json_lenient_comments() {
+    let expected = json("[1, {\"a\": true, \"b\": []}, \"x\"]");
+    assert_eq!(
+        expected,
+        json_lenient(
+            "[1 /* one */, // first\n \
+            {\"a\" /* key */ : /* value */ true, \"b\": [ /**/ ]}, \
+            \"x\" // last\n]"
+        )
+    );
+    assert_eq!(
+        expected,
+        json_lenient("/* header */ [1, {\"a\": true, \"b\": [],}, \"x\",] // end")
+    );
+    assert_eq!(
+        json("[\"// not a comment\"]"),
+        json_lenient("[\"// not a comment\"]")
+    );
+    assert_eq!(
+        json("{\"a\": 1, \"b\": 2}"),
+        json_lenient("{\"a\": 1, /* next */ \"b\": 2}")
+    );
+}
+
+#[test]
+fn json_lenient_rejects_malformed_comments() {
+    assert!(json_lenient("[1, /* unterminated ]").is_err());
+    assert!(json_lenient("[1 /* outer /* inner */ */]").is_err());
+    assert!(json_lenient("[1 / 2]").is_err());
+    assert!(json("[1 /* strict */]").is_err());
+    assert!(json("// strict\n[]").is_err());
+}
+
+#[test]
+fn json_recover_errors() {
+    let options = JsonOptions {
+        recover_errors: true,
+        ..JsonOptions::default()
+    };
+
+    assert_eq!(
+        Ok(Json::Array {
+            elem: vec!(
+                Json::Number { elem: "1" },
+                Json::Error { elem: "@@@" },
+                Json::Number { elem: "3" }
+            ),
+            text: Verbatim::default(),
+        }),
+        json_with("[1, @@@, 3]", &options)
+    );
+    assert!(json("[1, @@@, 3]").is_err());
+
+    assert_eq!(
+        Ok(Json::Object {
+            elem: vec!(
+                (Json::String { elem: "a" }, Json::Error { elem: "tru e" }),
+                (Json::String { elem: "b" }, Json::Null { elem: "null" })
+            ),
+            text: Verbatim::default(),
+        }),
+        json_with("{\"a\": tru e , \"b\": null}", &options)
+    );
+}
+
+#[test]
+fn json_recover_errors_skips_quoted_delimiters() {
+    let options = JsonOptions {
+        recover_errors: true,
+        ..JsonOptions::default()
+    };
+
+    assert_eq!(
+        Ok(Json::Array {
+            elem: vec!(
+                Json::Number { elem: "1" },
+                Json::Error { elem: "@\"x,y\"" },
+                Json::Number { elem: "3" }
+            ),
+            text: Verbatim::default(),
+        }),
+        json_with("[1, @\"x,y\", 3]", &options)
+    );
+    assert_eq!(
+        Ok(Json::Array {
+            elem: vec!(
+                Json::Number { elem: "1" },
+                Json::Error { elem: "@\"x]\"" },
+                Json::Number { elem: "3" }
+            ),
+            text: Verbatim::default(),
+        }),
+        json_with("[1, @\"x]\", 3]", &options)
+    );
+    assert_eq!(
+        Ok(Json::Array {
+            elem: vec!(
+                Json::Error { elem: "@\"a\\\",\"" },
+                Json::Null { elem: "null" }
+            ),
+            text: Verbatim::default(),
+        }),
+        json_with("[@\"a\\\",\", null]", &options)
+    );
+    assert!(json_with("[1, @\"x, 3]", &options).is_err());
+}
+
+#[test]
+fn json_recover_errors_needs_content() {
+    let options = JsonOptions {
+        recover_errors: true,
+        ..JsonOptions::default()
+    };
+
+    assert!(json_with("[1, , 3]", &options).is_err());
+    assert!(json_with("[1, 2,]", &options).is_err());
+}
+
 #[test]
 fn json_demo_sample() {
     assert!(json(include_str!("sample.json")).is_ok());
 }
+
+#[test]
+fn json_large_array() {
+    let source = format!(
+        "[{}]",
+        (0..10_000).map(|i| i.to_string()).collect::<Vec<_>>().join(", ")
+    );
+
+    match json(&source) {
+        Ok(Json::Array { elem, .. }) => {
+            assert_eq!(10_000, elem.len());
+            for (i, value) in elem.iter().enumerate() {
+                assert_eq!(Some(i as i64), value.as_i64());
+            }
+        }
+        other => panic!("Unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn json_options_are_independent() {
+    let with = |toggle: fn(&mut JsonOptions)| {
+        let mut options = JsonOptions::default();
+        toggle(&mut options);
+        options
+    };
+    let modes = [
+        ("[1, x]", with(|o| o.recover_errors = true)),
+        ("{\"a\": 1, \"a\": 2}", with(|o| o.reject_duplicate_keys = true)),
+        ("[[[]]]", with(|o| o.max_depth = Some(2))),
+        ("[1,]", with(|o| o.allow_trailing_commas = true)),
+        ("[1 /* c */]", with(|o| o.allow_comments = true)),
+    ];
+
+    for (input, _) in &modes {
+        assert_eq!(json(input), json_with(input, &JsonOptions::default()));
+    }
+
+    // Each option only changes the outcome of the input exercising it
+    for (own_input, options) in &modes {
+        for (input, _) in &modes {
+            let toggled = json_with(input, options).is_ok() != json(input).is_ok();
+            assert_eq!(input == own_input, toggled, "{:?} on {}", options, input);
+        }
+    }
+}
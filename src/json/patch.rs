@@ -0,0 +1,375 @@
+use super::pointer::{array_index, pointer_tokens};
+use super::unescape::escape;
+use super::*;
+use std::borrow::Cow;
+
+/// Splits a JSON pointer into the pointer to the parent and the unescaped last token.
+fn split_last(path: &str) -> Result<(&str, String), String> {
+    let malformed = || format!("Malformed pointer '{}'", path);
+
+    // Escaped tokens cannot contain a `/`, so the last one is always a separator
+    let (parent, last) = path.rsplit_once('/').ok_or_else(malformed)?;
+    let mut tokens = pointer_tokens(&format!("/{}", last)).ok_or_else(malformed)?;
+    pointer_tokens(parent).ok_or_else(malformed)?;
+
+    Ok((parent, tokens.remove(0)))
+}
+
+/// Whether two values are equal in the sense of the RFC 6902 `test` operation.
+///
+/// Numbers are compared by value, strings once their escape sequences are decoded and object
+/// members regardless of their order.
+fn same_value(a: &JsonOwned, b: &JsonOwned) -> bool {
+    match (a, b) {
+        (JsonOwned::Object { elem: a }, JsonOwned::Object { elem: b }) => {
+            a.len() == b.len()
+                && a.iter().all(|(key, value)| {
+                    b.iter()
+                        .any(|(k, v)| same_value(k, key) && same_value(value, v))
+                })
+        }
+        (JsonOwned::Array { elem: a }, JsonOwned::Array { elem: b }) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_value(a, b))
+        }
+        (JsonOwned::Number { elem: a }, JsonOwned::Number { elem: b }) => {
+            matches!((a.parse::<f64>(), b.parse::<f64>()), (Ok(a), Ok(b)) if a == b)
+        }
+        (JsonOwned::String { elem: a }, JsonOwned::String { elem: b }) => {
+            let decoded = |elem| Json::String { elem }.unescaped();
+            matches!((decoded(a), decoded(b)), (Some(a), Some(b)) if a == b)
+        }
+        _ => a == b,
+    }
+}
+
+/// Retrieves a member of a patch operation.
+fn member<'s, 'a>(operation: &'s Json<'a>, name: &str) -> Option<&'s Json<'a>> {
+    match operation {
+        Json::Object { elem, .. } => elem
+            .iter()
+            .find(|(k, _)| k.is_key(name))
+            .map(|(_, v)| v),
+        _ => None,
+    }
+}
+
+/// Retrieves a string member of a patch operation, failing if it is missing.
+fn str_member<'a>(operation: &Json<'a>, name: &str) -> Result<Cow<'a, str>, String> {
+    member(operation, name)
+        .and_then(Json::unescaped)
+        .ok_or_else(|| format!("Expected a string member '{}'", name))
+}
+
+impl JsonOwned {
+    /// Applies a JSON Patch (RFC 6902) document.
+    ///
+    /// Supports the `add`, `remove`, `replace`, `move`, `copy` and `test` operations. The patch
+    /// is applied atomically: if any operation fails, the document is left untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let mut owned = JsonOwned::from(&json("{\"a\": [1]}").unwrap());
+    /// let patch = json(
+    ///     "[{\"op\": \"add\", \"path\": \"/a/-\", \"value\": 2}, \
+    ///     {\"op\": \"test\", \"path\": \"/a/1\", \"value\": 2}]",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(Ok(()), owned.apply_patch(&patch));
+    /// assert_eq!(JsonOwned::from(&json("{\"a\": [1, 2]}").unwrap()), owned);
+    /// ```
+    pub fn apply_patch(&mut self, patch: &Json) -> Result<(), String> {
+        let operations = match patch {
+            Json::Array { elem, .. } => elem,
+            _ => return Err("Expected an array of operations".to_string()),
+        };
+
+        let mut patched = self.clone();
+        for (idx, operation) in operations.iter().enumerate() {
+            patched
+                .apply_operation(operation)
+                .map_err(|err| format!("Operation {}: {}", idx, err))?;
+        }
+
+        *self = patched;
+        Ok(())
+    }
+
+    /// Applies a single operation of a patch.
+    fn apply_operation(&mut self, operation: &Json) -> Result<(), String> {
+        let path = str_member(operation, "path")?;
+        let path = path.as_ref();
+        let value = || {
+            member(operation, "value")
+                .map(JsonOwned::from)
+                .ok_or_else(|| "Expected a member 'value'".to_string())
+        };
+
+        match str_member(operation, "op")?.as_ref() {
+            "add" => self.add(path, value()?),
+            "remove" => self.remove(path).map(|_| ()),
+            "replace" => {
+                let node = self
+                    .pointer_mut(path)
+                    .ok_or_else(|| format!("Path '{}' does not exist", path))?;
+                *node = value()?;
+                Ok(())
+            }
+            "move" => {
+                let from = str_member(operation, "from")?;
+                let from = from.as_ref();
+                if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                    return Err(format!("Cannot move '{}' into one of its children", from));
+                }
+                let moved = self.remove(from)?;
+                self.add(path, moved)
+            }
+            "copy" => {
+                let from = str_member(operation, "from")?;
+                let from = from.as_ref();
+                let copied = self
+                    .pointer(from)
+                    .cloned()
+                    .ok_or_else(|| format!("Path '{}' does not exist", from))?;
+                self.add(path, copied)
+            }
+            "test" => match self.pointer(path) {
+                Some(node) if same_value(node, &value()?) => Ok(()),
+                _ => Err(format!("Test failed at '{}'", path)),
+            },
+            op => Err(format!("Unknown operation '{}'", op)),
+        }
+    }
+
+    /// Adds a value, replacing the whole document for the root pointer.
+    fn add(&mut self, path: &str, value: JsonOwned) -> Result<(), String> {
+        if path.is_empty() {
+            *self = value;
+            return Ok(());
+        }
+
+        let (parent, token) = split_last(path)?;
+        match self.pointer_mut(parent) {
+            Some(Self::Object { elem }) => {
+                match elem.iter_mut().find(|(k, _)| k.is_key(&token)) {
+                    Some((_, existing)) => *existing = value,
+                    None => elem.push((
+                        Self::String {
+                            elem: escape(&token),
+                        },
+                        value,
+                    )),
+                }
+                Ok(())
+            }
+            Some(Self::Array { elem }) => {
+                let idx = match token.as_str() {
+                    "-" => elem.len(),
+                    _ => array_index(&token)
+                        .filter(|idx| *idx <= elem.len())
+                        .ok_or_else(|| format!("Index '{}' is out of bounds", token))?,
+                };
+                elem.insert(idx, value);
+                Ok(())
+            }
+            _ => Err(format!("Path '{}' does not exist", parent)),
+        }
+    }
+
+    /// Removes a value and returns it.
+    fn remove(&mut self, path: &str) -> Result<JsonOwned, String> {
+        let missing = || format!("Path '{}' does not exist", path);
+        if path.is_empty() {
+            return Err("Cannot remove the root".to_string());
+        }
+
+        let (parent, token) = split_last(path)?;
+        match self.pointer_mut(parent) {
+            Some(Self::Object { elem }) => {
+                let idx = elem
+                    .iter()
+                    .position(|(k, _)| k.is_key(&token))
+                    .ok_or_else(missing)?;
+                Ok(elem.remove(idx).1)
+            }
+            Some(Self::Array { elem }) => {
+                let idx = array_index(&token)
+                    .filter(|idx| *idx < elem.len())
+                    .ok_or_else(missing)?;
+                Ok(elem.remove(idx))
+            }
+            _ => Err(missing()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owned(source: &str) -> JsonOwned {
+        JsonOwned::from(&json(source).unwrap())
+    }
+
+    fn patched(document: &str, patch: &str) -> Result<JsonOwned, String> {
+        let mut document = owned(document);
+        document.apply_patch(&json(patch).unwrap())?;
+        Ok(document)
+    }
+
+    #[test]
+    fn test_patch_add() {
+        assert_eq!(
+            Ok(owned("{\"a\": 1, \"b\": [true, null, false]}")),
+            patched(
+                "{\"a\": 1, \"b\": [true, false]}",
+                "[{\"op\": \"add\", \"path\": \"/b/1\", \"value\": null}]"
+            )
+        );
+        assert_eq!(
+            Ok(owned("{\"a\": 2}")),
+            patched(
+                "{\"a\": 1}",
+                "[{\"op\": \"add\", \"path\": \"/a\", \"value\": 2}]"
+            )
+        );
+        assert_eq!(
+            Ok(owned("[]")),
+            patched("{}", "[{\"op\": \"add\", \"path\": \"\", \"value\": []}]")
+        );
+    }
+
+    #[test]
+    fn test_patch_add_out_of_bounds() {
+        assert_eq!(
+            Err("Operation 0: Index '3' is out of bounds".to_string()),
+            patched(
+                "[1, 2]",
+                "[{\"op\": \"add\", \"path\": \"/3\", \"value\": 3}]"
+            )
+        );
+        assert_eq!(
+            Err("Operation 0: Path '/missing' does not exist".to_string()),
+            patched(
+                "{}",
+                "[{\"op\": \"add\", \"path\": \"/missing/a\", \"value\": 3}]"
+            )
+        );
+    }
+
+    #[test]
+    fn test_patch_remove() {
+        assert_eq!(
+            Ok(owned("{\"b\": [2]}")),
+            patched(
+                "{\"a\": 1, \"b\": [1, 2]}",
+                "[{\"op\": \"remove\", \"path\": \"/a\"}, {\"op\": \"remove\", \"path\": \"/b/0\"}]"
+            )
+        );
+        assert_eq!(
+            Err("Operation 0: Path '/2' does not exist".to_string()),
+            patched("[1, 2]", "[{\"op\": \"remove\", \"path\": \"/2\"}]")
+        );
+    }
+
+    #[test]
+    fn test_patch_replace() {
+        assert_eq!(
+            Ok(owned("{\"a\": {\"b\": \"c\"}}")),
+            patched(
+                "{\"a\": 1}",
+                "[{\"op\": \"replace\", \"path\": \"/a\", \"value\": {\"b\": \"c\"}}]"
+            )
+        );
+        assert!(patched(
+            "{}",
+            "[{\"op\": \"replace\", \"path\": \"/a\", \"value\": 1}]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_patch_move() {
+        assert_eq!(
+            Ok(owned("{\"b\": {\"c\": [1]}}")),
+            patched(
+                "{\"a\": [1], \"b\": {}}",
+                "[{\"op\": \"move\", \"from\": \"/a\", \"path\": \"/b/c\"}]"
+            )
+        );
+        assert_eq!(
+            Err("Operation 0: Cannot move '/a' into one of its children".to_string()),
+            patched(
+                "{\"a\": {}}",
+                "[{\"op\": \"move\", \"from\": \"/a\", \"path\": \"/a/b\"}]"
+            )
+        );
+    }
+
+    #[test]
+    fn test_patch_copy() {
+        assert_eq!(
+            Ok(owned("{\"a\": [1], \"b\": [[1]]}")),
+            patched(
+                "{\"a\": [1], \"b\": []}",
+                "[{\"op\": \"copy\", \"from\": \"/a\", \"path\": \"/b/-\"}]"
+            )
+        );
+    }
+
+    #[test]
+    fn test_patch_test() {
+        assert_eq!(
+            Ok(owned("{\"a\": {\"x\": 1, \"y\": 2}}")),
+            patched(
+                "{\"a\": {\"x\": 1, \"y\": 2}}",
+                "[{\"op\": \"test\", \"path\": \"/a\", \"value\": {\"y\": 2.0, \"x\": 1}}]"
+            )
+        );
+        assert_eq!(
+            Err("Operation 0: Test failed at '/a'".to_string()),
+            patched(
+                "{\"a\": 1}",
+                "[{\"op\": \"test\", \"path\": \"/a\", \"value\": \"1\"}]"
+            )
+        );
+    }
+
+    #[test]
+    fn test_patch_is_atomic() {
+        let mut document = owned("{\"a\": 1}");
+        let patch = json(
+            "[{\"op\": \"remove\", \"path\": \"/a\"}, \
+            {\"op\": \"test\", \"path\": \"/a\", \"value\": 1}]",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Err("Operation 1: Test failed at '/a'".to_string()),
+            document.apply_patch(&patch)
+        );
+        assert_eq!(owned("{\"a\": 1}"), document);
+        assert!(document.apply_patch(&json("{}").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_patch_decodes_escapes() {
+        assert_eq!(
+            Ok(owned("{\"b\": 1}")),
+            patched(
+                "{\"a\\/b\": 1}",
+                "[{\"op\": \"move\", \"from\": \"/a~1b\", \"path\": \"\\/b\"}]"
+            )
+        );
+        assert_eq!(
+            Ok(owned("{\"caf\\u00e9\": \"A\", \"q\\\"\": 1}")),
+            patched(
+                "{\"caf\\u00e9\": \"A\"}",
+                "[{\"op\": \"test\", \"path\": \"/caf\u{e9}\", \"value\": \"\\u0041\"}, \
+                {\"op\": \"add\", \"path\": \"/q\\\"\", \"value\": 1}]"
+            )
+        );
+    }
+}
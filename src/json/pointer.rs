@@ -0,0 +1,233 @@
+use super::*;
+
+/// Splits a JSON pointer (RFC 6901) into its unescaped reference tokens.
+///
+/// Returns None if the pointer is malformed, that is when it is neither empty nor starts with a
+/// `/`, or when it contains a `~` that is not followed by `0` or `1`.
+pub(crate) fn pointer_tokens(ptr: &str) -> Option<Vec<String>> {
+    if ptr.is_empty() {
+        return Some(Vec::new());
+    }
+
+    ptr.strip_prefix('/')?
+        .split('/')
+        .map(|token| {
+            // `~1` must be decoded before `~0` so that `~01` yields `~1` and not `/`
+            let valid = token
+                .match_indices('~')
+                .all(|(idx, _)| matches!(token.as_bytes().get(idx + 1), Some(b'0' | b'1')));
+
+            valid.then(|| token.replace("~1", "/").replace("~0", "~"))
+        })
+        .collect()
+}
+
+/// Escapes a key so that it can be used as a reference token of a JSON pointer.
+pub(crate) fn escape_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Converts a reference token into an array index.
+///
+/// Leading zeros are not allowed, and neither is the `-` past-the-end token.
+pub(crate) fn array_index(token: &str) -> Option<usize> {
+    let is_canonical = token == "0" || !token.starts_with('0');
+    let is_digits = !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit());
+
+    if is_canonical && is_digits {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+impl JsonOwned {
+    /// Whether the node is a string whose decoded content is equal to the given key.
+    pub(crate) fn is_key(&self, key: &str) -> bool {
+        matches!(self, Self::String { elem } if Json::String { elem }.is_key(key))
+    }
+
+    /// Retrieves the node a JSON pointer (RFC 6901) refers to.
+    ///
+    /// Returns None for malformed pointers, missing keys, out of range indices and when the
+    /// pointer goes through a terminal value.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let owned = JsonOwned::from(&json("{\"a/b\": [true]}").unwrap());
+    /// assert_eq!(
+    ///     Some(&JsonOwned::True { elem: "true".to_string() }),
+    ///     owned.pointer("/a~1b/0")
+    /// );
+    /// ```
+    pub fn pointer(&self, ptr: &str) -> Option<&JsonOwned> {
+        pointer_tokens(ptr)?
+            .iter()
+            .try_fold(self, |node, token| match node {
+                Self::Object { elem } => elem.iter().find(|(k, _)| k.is_key(token)).map(|(_, v)| v),
+                Self::Array { elem } => elem.get(array_index(token)?),
+                _ => None,
+            })
+    }
+
+    /// Mutable counterpart of [`JsonOwned::pointer`].
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let mut owned = JsonOwned::from(&json("[1, 2]").unwrap());
+    /// *owned.pointer_mut("/1").unwrap() = JsonOwned::Null { elem: "null".to_string() };
+    /// assert_eq!(JsonOwned::from(&json("[1, null]").unwrap()), owned);
+    /// ```
+    pub fn pointer_mut(&mut self, ptr: &str) -> Option<&mut JsonOwned> {
+        pointer_tokens(ptr)?
+            .iter()
+            .try_fold(self, |node, token| match node {
+                Self::Object { elem } => elem
+                    .iter_mut()
+                    .find(|(k, _)| k.is_key(token))
+                    .map(|(_, v)| v),
+                Self::Array { elem } => elem.get_mut(array_index(token)?),
+                _ => None,
+            })
+    }
+}
+
+impl<'a> Json<'a> {
+    /// Whether the node is a string whose decoded content is equal to the given key.
+    pub(crate) fn is_key(&self, key: &str) -> bool {
+        matches!(self.unescaped(), Some(decoded) if decoded == key)
+    }
+
+    /// Retrieves the node a JSON pointer (RFC 6901) refers to.
+    ///
+    /// Returns None for malformed pointers, missing keys, out of range indices and when the
+    /// pointer goes through a terminal value.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("{\"a/b\": [true]}").unwrap();
+    /// assert_eq!(Some(&Json::True { elem: "true" }), parsed.pointer("/a~1b/0"));
+    /// ```
+    pub fn pointer(&self, ptr: &str) -> Option<&Json<'a>> {
+        pointer_tokens(ptr)?
+            .iter()
+            .try_fold(self, |node, token| match node {
+                Self::Object { elem, .. } => {
+                    elem.iter().find(|(k, _)| k.is_key(token)).map(|(_, v)| v)
+                }
+                Self::Array { .. } => node.index(array_index(token)?),
+                _ => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pointer_tokens() {
+        assert_eq!(Some(vec!()), pointer_tokens(""));
+        assert_eq!(Some(vec!("".to_string())), pointer_tokens("/"));
+        assert_eq!(
+            Some(vec!("a/b".to_string(), "m~n".to_string(), "~1".to_string())),
+            pointer_tokens("/a~1b/m~0n/~01")
+        );
+        assert_eq!(None, pointer_tokens("a"));
+        assert_eq!(None, pointer_tokens("/a~2"));
+        assert_eq!(None, pointer_tokens("/a~"));
+    }
+
+    #[test]
+    fn test_escape_token() {
+        assert_eq!("a~1b~0c", escape_token("a/b~c"));
+        assert_eq!(Some(vec!("a/b~c".to_string())), pointer_tokens("/a~1b~0c"));
+    }
+
+    #[test]
+    fn test_array_index() {
+        assert_eq!(Some(0), array_index("0"));
+        assert_eq!(Some(10), array_index("10"));
+        assert_eq!(None, array_index("01"));
+        assert_eq!(None, array_index("-"));
+        assert_eq!(None, array_index("+1"));
+        assert_eq!(None, array_index(""));
+    }
+
+    #[test]
+    fn test_owned_pointer() {
+        let owned = JsonOwned::from(&json("{\"a\": [{\"b\": null}], \"\": 1}").unwrap());
+        assert_eq!(Some(&owned), owned.pointer(""));
+        assert_eq!(
+            Some(&JsonOwned::Null {
+                elem: "null".into()
+            }),
+            owned.pointer("/a/0/b")
+        );
+        assert_eq!(
+            Some(&JsonOwned::Number { elem: "1".into() }),
+            owned.pointer("/")
+        );
+        assert_eq!(None, owned.pointer("/a/1"));
+        assert_eq!(None, owned.pointer("/a/0/b/c"));
+        assert_eq!(None, owned.pointer("/b"));
+    }
+
+    #[test]
+    fn test_pointer_sample() {
+        let parsed = json(include_str!("sample.json")).unwrap();
+        assert_eq!(
+            Some(&Json::String { elem: "cofaxCDS" }),
+            parsed.pointer("/web-app/servlet/0/servlet-name")
+        );
+        assert_eq!(
+            Some(&Json::String { elem: "/admin/*" }),
+            parsed.pointer("/web-app/servlet-mapping/cofaxAdmin")
+        );
+        assert_eq!(None, parsed.pointer("/web-app/servlet/99"));
+        assert_eq!(None, parsed.pointer("/web-app/missing"));
+        assert_eq!(None, parsed.pointer("/web-app/servlet/0/servlet-name/0"));
+        assert_eq!(None, parsed.pointer("web-app"));
+    }
+
+    #[test]
+    fn test_pointer_escapes() {
+        let parsed = json("{\"a/b\": 1, \"m~n\": 2, \"~1\": 3, \"a\": {\"b\": 4}}").unwrap();
+        assert_eq!(Some(&Json::Number { elem: "1" }), parsed.pointer("/a~1b"));
+        assert_eq!(Some(&Json::Number { elem: "2" }), parsed.pointer("/m~0n"));
+        assert_eq!(Some(&Json::Number { elem: "3" }), parsed.pointer("/~01"));
+        assert_eq!(Some(&Json::Number { elem: "4" }), parsed.pointer("/a/b"));
+        assert_eq!(None, parsed.pointer("/m~2n"));
+    }
+
+    #[test]
+    fn test_pointer_decodes_keys() {
+        let source = "{\"a\\/b\": 1, \"caf\\u00e9\": {\"\\\"\": 2}, \"\\ud800\": 3}";
+        let parsed = json(source).unwrap();
+        assert_eq!(Some(&Json::Number { elem: "1" }), parsed.pointer("/a~1b"));
+        assert_eq!(Some(&Json::Number { elem: "2" }), parsed.pointer("/caf\u{e9}/\""));
+        assert_eq!(None, parsed.pointer("/a\\~1b"));
+        assert_eq!(None, parsed.pointer("/\\ud800"));
+
+        let mut owned = JsonOwned::from(&parsed);
+        assert_eq!(
+            Some(&JsonOwned::Number { elem: "1".into() }),
+            owned.pointer("/a~1b")
+        );
+        *owned.pointer_mut("/caf\u{e9}/\"").unwrap() = JsonOwned::Null {
+            elem: "null".into(),
+        };
+        assert_eq!(
+            Some(&JsonOwned::Null {
+                elem: "null".into()
+            }),
+            owned.pointer("/caf\u{e9}/\"")
+        );
+    }
+}
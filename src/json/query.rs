@@ -0,0 +1,104 @@
+use super::*;
+
+/// Percent-encodes a string as specified by `application/x-www-form-urlencoded`.
+fn form_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+impl<'a> Json<'a> {
+    /// Encodes a flat object as a `key=value&key2=value2` query string.
+    ///
+    /// Keys and values are percent-encoded as `application/x-www-form-urlencoded`, strings once
+    /// their escape sequences are decoded. Returns None if the node is not an object, if any value
+    /// is not a string, a number or a boolean, or if a string holds an invalid escape.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("{\"q\": \"rust parser\", \"page\": 2, \"all\": true}").unwrap();
+    /// assert_eq!(
+    ///     Some("q=rust+parser&page=2&all=true".to_string()),
+    ///     parsed.to_query_string()
+    /// );
+    /// assert_eq!(None, json("{\"a\": [1]}").unwrap().to_query_string());
+    /// ```
+    pub fn to_query_string(&self) -> Option<String> {
+        let pairs = match self {
+            Self::Object { elem, .. } => elem,
+            _ => return None,
+        };
+
+        let encoded = pairs
+            .iter()
+            .map(|(key, value)| {
+                let key = key.unescaped()?;
+                let value = match value {
+                    Self::String { .. } => value.unescaped()?,
+                    Self::Number { elem } | Self::True { elem } | Self::False { elem } => {
+                        (*elem).into()
+                    }
+                    _ => return None,
+                };
+                Some(format!("{}={}", form_encode(&key), form_encode(&value)))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(encoded.join("&"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_query_string_flat() {
+        let parsed = json("{\"name\": \"bob\", \"age\": -1.5, \"admin\": false}").unwrap();
+        assert_eq!(
+            Some("name=bob&age=-1.5&admin=false".to_string()),
+            parsed.to_query_string()
+        );
+        assert_eq!(Some("".to_string()), json("{}").unwrap().to_query_string());
+    }
+
+    #[test]
+    fn test_to_query_string_not_flat() {
+        assert_eq!(None, json("{\"a\": {\"b\": 1}}").unwrap().to_query_string());
+        assert_eq!(None, json("{\"a\": null}").unwrap().to_query_string());
+        assert_eq!(None, json("[1]").unwrap().to_query_string());
+    }
+
+    #[test]
+    fn test_to_query_string_encoding() {
+        let parsed = json("{\"a&b\": \"x=1 + y/z\", \"~*\": \"100%\"}").unwrap();
+        assert_eq!(
+            Some("a%26b=x%3D1+%2B+y%2Fz&%7E*=100%25".to_string()),
+            parsed.to_query_string()
+        );
+
+        let parsed = Json::Object {
+            elem: vec![(Json::String { elem: "café" }, Json::True { elem: "true" })],
+            text: Verbatim::default(),
+        };
+        assert_eq!(Some("caf%C3%A9=true".to_string()), parsed.to_query_string());
+    }
+
+    #[test]
+    fn test_to_query_string_escapes() {
+        let parsed = json("{\"msg\": \"a\\nb\", \"q\\\"\": \"caf\\u00e9\"}").unwrap();
+        assert_eq!(
+            Some("msg=a%0Ab&q%22=caf%C3%A9".to_string()),
+            parsed.to_query_string()
+        );
+        assert_eq!(None, json("{\"a\": \"\\ud800\"}").unwrap().to_query_string());
+    }
+}
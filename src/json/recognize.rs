@@ -0,0 +1,73 @@
+use super::parser::document_with;
+use super::JsonOptions;
+
+/// Checks whether a slice represents valid Json, without building it.
+///
+/// Runs the parser of [`json`](super::json), discarding the elements of arrays and objects as
+/// soon as they are recognized. No vector is built, which cuts down allocations, the combinators
+/// still allocate their error messages.
+///
+/// # Example
+/// ```
+/// use parser_combinator::json::*;
+///
+/// assert!(is_valid_json("{\"a\": [1, true, null]}"));
+/// assert!(!is_valid_json("{\"a\": [1, true, null}"));
+/// ```
+pub fn is_valid_json(source: &str) -> bool {
+    document_with(source, &JsonOptions::default(), true).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{json, Json};
+
+    #[test]
+    fn test_is_valid_json_agrees_with_json() {
+        let inputs = [
+            "{}",
+            "[]",
+            "[\"bar\", \"foo\", true, {\"name\": \"bob\"}]",
+            "{ \"value\": [\"bar\", {\"name\": \"bob\"}], \"n\": -1.5e3 }",
+            include_str!("sample.json"),
+            "",
+            "true",
+            "[",
+            "[1,]",
+            "[1 2]",
+            "{\"a\" 1}",
+            "{1: 2}",
+            "[\"unterminated]",
+            "[] trailing",
+            "{}}",
+            "[0123]",
+            "[TRUE]",
+            "[1 /* strict */]",
+            "{\"a\": 1, \"a\": 2}",
+        ];
+
+        for input in inputs {
+            assert_eq!(json(input).is_ok(), is_valid_json(input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_is_valid_json_large_documents() {
+        let inputs = [
+            format!("[{}]", vec!("1"; 10_000).join(", ")),
+            format!("{}{}", "[{\"a\": ".repeat(100), "}]".repeat(100)),
+            format!("{}{}", "[".repeat(100), "]".repeat(99)),
+        ];
+
+        for input in &inputs {
+            assert_eq!(json(input).is_ok(), is_valid_json(input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_discarded_elements() {
+        let discarded = document_with("[1, {\"a\": [2]}]", &JsonOptions::default(), true);
+        assert!(matches!(discarded, Ok(Json::Array { elem, .. }) if elem.is_empty()));
+    }
+}
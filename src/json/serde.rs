@@ -0,0 +1,78 @@
+use super::*;
+use serde_json::{Map, Value};
+
+/// Decoded content of a string, falling back to its raw text if it holds an invalid escape.
+fn decoded(json: &Json, raw: &str) -> String {
+    json.unescaped()
+        .map(|text| text.into_owned())
+        .unwrap_or_else(|| raw.to_string())
+}
+
+/// Converts the Json into a `serde_json::Value`, decoding the escape sequences of strings.
+///
+/// Numbers that do not fit in a `serde_json::Number`, such as `1e400`, become `null`, and so do
+/// error nodes. When an object holds the same key more than once, the last value wins.
+///
+/// # Example
+/// ```
+/// use parser_combinator::json::*;
+///
+/// let parsed = json("{\"a\": [1, \"\\u0041\"]}").unwrap();
+/// assert_eq!(serde_json::json!({"a": [1, "A"]}), serde_json::Value::from(&parsed));
+/// ```
+impl<'a> From<&Json<'a>> for Value {
+    fn from(json: &Json<'a>) -> Self {
+        match json {
+            Json::Object { elem, .. } => {
+                let mut map = Map::new();
+                for (key, value) in elem {
+                    if let Json::String { elem } = key {
+                        map.insert(decoded(key, elem), value.into());
+                    }
+                }
+                Value::Object(map)
+            }
+            Json::Array { elem, .. } => Value::Array(elem.iter().map(Value::from).collect()),
+            Json::Number { elem } => elem.parse().map(Value::Number).unwrap_or(Value::Null),
+            Json::String { elem } => Value::String(decoded(json, elem)),
+            Json::True { .. } => Value::Bool(true),
+            Json::False { .. } => Value::Bool(false),
+            Json::Null { .. } | Json::Error { .. } => Value::Null,
+        }
+    }
+}
+
+/// Same as the conversion from a reference.
+impl<'a> From<Json<'a>> for Value {
+    fn from(json: Json<'a>) -> Self {
+        Self::from(&json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_from_sample() {
+        let source = include_str!("sample.json");
+        let expected: Value = serde_json::from_str(source).unwrap();
+        assert_eq!(expected, Value::from(json(source).unwrap()));
+    }
+
+    #[test]
+    fn test_value_from_terminals() {
+        let parsed = json("[-1.5e3, 42, \"tab\\tand \\uD83D\\uDE00\", true, false, null]").unwrap();
+        let expected: Value =
+            serde_json::from_str("[-1500.0, 42, \"tab\\tand 😀\", true, false, null]").unwrap();
+        assert_eq!(expected, Value::from(&parsed));
+        assert_eq!(Value::Null, Value::from(Json::Number { elem: "1e400" }));
+    }
+
+    #[test]
+    fn test_value_from_duplicate_keys() {
+        let source = "{\"a\": 1, \"a\\u0062\": 2, \"ab\": 3}";
+        let expected: Value = serde_json::from_str(source).unwrap();
+        assert_eq!(expected, Value::from(json(source).unwrap()));
+    }
+}
@@ -0,0 +1,185 @@
+use super::*;
+
+/// Depth-first pre-order iterator over the nodes of a Json tree.
+///
+/// # Note
+/// Object keys are not visited, only their values.
+pub(crate) struct Walk<'s, 'a> {
+    stack: Vec<&'s Json<'a>>,
+}
+
+impl<'s, 'a> Walk<'s, 'a> {
+    pub(crate) fn new(root: &'s Json<'a>) -> Self {
+        Self { stack: vec![root] }
+    }
+}
+
+impl<'s, 'a> Iterator for Walk<'s, 'a> {
+    type Item = &'s Json<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+
+        // Pushed in reverse so that children are yielded in document order
+        self.stack.extend(node.children().rev());
+        Some(node)
+    }
+}
+
+impl<'a> Json<'a> {
+    /// Iterates over the direct children of the node in document order.
+    ///
+    /// Yields the values of an object, not its keys, and the elements of an array. Terminal
+    /// values have no children.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("{\"a\": 1, \"b\": [2, 3]}").unwrap();
+    /// let children: Vec<_> = parsed.children().collect();
+    /// assert_eq!(vec!(&Json::Number { elem: "1" }, &json("[2, 3]").unwrap()), children);
+    /// assert_eq!(0, Json::Null { elem: "null" }.children().count());
+    /// ```
+    pub fn children(&self) -> impl DoubleEndedIterator<Item = &Json<'a>> + '_ {
+        let (pairs, values): (&[_], &[_]) = match self {
+            Self::Object { elem, .. } => (elem, &[]),
+            Self::Array { elem, .. } => (&[], elem),
+            _ => (&[], &[]),
+        };
+        pairs.iter().map(|(_, value)| value).chain(values)
+    }
+
+    /// Iterates depth-first over the whole tree in document order, the node itself first.
+    ///
+    /// Like `children`, object keys are not visited.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("[1, [true], {\"a\": null}]").unwrap();
+    /// assert_eq!(6, parsed.walk().count());
+    /// ```
+    pub fn walk(&self) -> impl Iterator<Item = &Json<'a>> + '_ {
+        Walk::new(self)
+    }
+
+    /// Iterates over the terminal values of the tree in document order.
+    ///
+    /// Objects and arrays themselves are skipped, only their content is yielded. Object keys are
+    /// not considered leaves.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("[1, [true], {\"a\": null}]").unwrap();
+    /// assert_eq!(3, parsed.leaves().count());
+    /// ```
+    pub fn leaves(&self) -> impl Iterator<Item = &Json<'a>> + '_ {
+        self.walk().filter(|node| !matches!(node, Self::Object { .. } | Self::Array { .. }))
+    }
+
+    /// Counts the nodes of the tree, at any depth, which satisfy the predicate.
+    ///
+    /// The node itself is included in the count if it satisfies the predicate.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("[1, [2, \"3\"], {\"a\": 4}]").unwrap();
+    /// assert_eq!(3, parsed.count_where(|node| matches!(node, Json::Number { .. })));
+    /// ```
+    pub fn count_where<FN>(&self, pred: FN) -> usize
+    where
+        FN: Fn(&Json<'a>) -> bool,
+    {
+        self.walk().filter(|node| pred(node)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_children() {
+        let parsed = json("[{\"k\": \"v\"}, [], \"s\"]").unwrap();
+        let kinds: Vec<_> = parsed.children().map(Json::entry_count).collect();
+        assert_eq!(vec!(1, 0, 0), kinds);
+        assert_eq!(
+            vec!(&Json::String { elem: "v" }),
+            parsed.index(0).unwrap().children().collect::<Vec<_>>()
+        );
+        assert_eq!(0, parsed.index(1).unwrap().children().count());
+    }
+
+    #[test]
+    fn test_walk_sample() {
+        let parsed = json(include_str!("sample.json")).unwrap();
+        let is_string = |node: &&Json| matches!(node, Json::String { .. });
+        assert_eq!(53, parsed.walk().filter(is_string).count());
+        assert_eq!(87, parsed.walk().count());
+        assert_eq!(Some(&parsed), parsed.walk().next());
+    }
+
+    #[test]
+    fn test_leaves() {
+        let parsed = json(
+            "{ \
+    \"value\": [\
+        \"bar\", \
+        \"foo\", \
+        true, \
+        {\"name\": \"bob\"}\
+    ]}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec!(
+                &Json::String { elem: "bar" },
+                &Json::String { elem: "foo" },
+                &Json::True { elem: "true" },
+                &Json::String { elem: "bob" },
+            ),
+            parsed.leaves().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_leaves_empty_containers() {
+        let parsed = json("[[], {}, [[]]]").unwrap();
+        assert_eq!(0, parsed.leaves().count());
+    }
+
+    #[test]
+    fn test_count_where() {
+        let parsed = json(
+            "{ \
+    \"value\": [\
+        \"bar\", \
+        \"foo\", \
+        true, \
+        {\"name\": \"bob\"}\
+    ]}",
+        )
+        .unwrap();
+
+        let is_number = |node: &Json| matches!(node, Json::Number { .. });
+        assert_eq!(0, parsed.count_where(is_number));
+        assert_eq!(
+            3,
+            parsed.count_where(|node| matches!(node, Json::String { .. }))
+        );
+        assert_eq!(
+            2,
+            parsed.count_where(|node| matches!(node, Json::Object { .. }))
+        );
+
+        let parsed = json("[1, [2.5, [-3]], {\"n\": 4e2}]").unwrap();
+        assert_eq!(4, parsed.count_where(is_number));
+    }
+}
@@ -0,0 +1,148 @@
+use super::*;
+use std::borrow::Cow;
+use std::str::Chars;
+
+/// Reads the 4 hexadecimal digits of a `\uXXXX` escape.
+fn hex_code(chars: &mut Chars) -> Option<u32> {
+    (0..4).try_fold(0, |code, _| Some(code * 16 + chars.next()?.to_digit(16)?))
+}
+
+/// Decodes a `\uXXXX` escape whose `\u` was already consumed, combining surrogate pairs.
+fn unicode_escape(chars: &mut Chars) -> Option<char> {
+    let code = hex_code(chars)?;
+    if !(0xD800..0xDC00).contains(&code) {
+        // Lone low surrogates are rejected by `from_u32`
+        return char::from_u32(code);
+    }
+
+    // A high surrogate must be directly followed by a low one
+    if chars.next()? != '\\' || chars.next()? != 'u' {
+        return None;
+    }
+    let low = hex_code(chars).filter(|low| (0xDC00..0xE000).contains(low))?;
+    char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00))
+}
+
+/// Escapes a text so that it can be held by a `string` terminal, the reverse of
+/// [`Json::unescaped`].
+///
+/// Only quotes, backslashes and control characters are escaped.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                escaped.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl<'a> Json<'a> {
+    /// Content of a `string` terminal with its escape sequences decoded.
+    ///
+    /// Handles the escapes defined by RFC 8259, including `\uXXXX` and UTF-16 surrogate pairs.
+    /// The slice is borrowed as is when it does not contain any escape. Returns None for any
+    /// other variant, or if the string holds an invalid escape such as a lone surrogate.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("[\"\\u0041\\tb\"]").unwrap();
+    /// let Json::Array { elem, .. } = parsed else {
+    ///     panic!("Expected an array")
+    /// };
+    /// assert_eq!(Some("A\tb".into()), elem[0].unescaped());
+    /// ```
+    pub fn unescaped(&self) -> Option<Cow<'a, str>> {
+        let elem = match self {
+            Self::String { elem } => *elem,
+            _ => return None,
+        };
+        if !elem.contains('\\') {
+            return Some(Cow::Borrowed(elem));
+        }
+
+        let mut decoded = String::with_capacity(elem.len());
+        let mut chars = elem.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                decoded.push(c);
+                continue;
+            }
+
+            let c = match chars.next()? {
+                c @ ('"' | '\\' | '/') => c,
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'u' => unicode_escape(&mut chars)?,
+                _ => return None,
+            };
+            decoded.push(c);
+        }
+
+        Some(Cow::Owned(decoded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unescaped(elem: &str) -> Option<Cow<'_, str>> {
+        Json::String { elem }.unescaped()
+    }
+
+    #[test]
+    fn test_unescaped_borrows_without_escapes() {
+        assert!(matches!(unescaped("plain"), Some(Cow::Borrowed("plain"))));
+        assert_eq!(None, Json::Null { elem: "null" }.unescaped());
+    }
+
+    #[test]
+    fn test_unescaped_simple_escapes() {
+        assert_eq!(
+            Some("\"a\\/b\"\n\r\t\u{8}\u{c}".into()),
+            unescaped("\\\"a\\\\\\/b\\\"\\n\\r\\t\\b\\f")
+        );
+        assert_eq!(None, unescaped("\\x"));
+        assert_eq!(None, unescaped("trailing\\"));
+    }
+
+    #[test]
+    fn test_unescaped_unicode() {
+        assert_eq!(Some("A".into()), unescaped("\\u0041"));
+        assert_eq!(Some("café".into()), unescaped("caf\\u00e9"));
+        assert_eq!(Some("😀".into()), unescaped("\\uD83D\\uDE00"));
+        assert_eq!(None, unescaped("\\u00G1"));
+        assert_eq!(None, unescaped("\\u004"));
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        assert_eq!("plain", escape("plain"));
+        assert_eq!("a\\\"b\\\\c\\n\\u0001", escape("a\"b\\c\n\u{1}"));
+        for text in ["a/b", "q\"\\", "tab\there", "caf\u{e9} \u{1}"] {
+            assert_eq!(Some(text.into()), unescaped(&escape(text)));
+        }
+    }
+
+    #[test]
+    fn test_unescaped_lone_surrogates() {
+        assert_eq!(None, unescaped("\\uD83D"));
+        assert_eq!(None, unescaped("\\uD83Dx"));
+        assert_eq!(None, unescaped("\\uD83D\\u0041"));
+        assert_eq!(None, unescaped("\\uDE00"));
+    }
+}
@@ -0,0 +1,197 @@
+use super::*;
+
+/// Error returned by the schema-light validation helpers of [`Json`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError<'a> {
+    /// The node is not of the expected variant.
+    WrongVariant {
+        /// Name of the variant that was expected, e.g. `"array"`.
+        expected: &'static str,
+    },
+
+    /// An array element did not satisfy the predicate.
+    InvalidElement {
+        /// Index of the first failing element.
+        index: usize,
+    },
+
+    /// An object value did not satisfy the predicate.
+    InvalidValue {
+        /// Key of the first failing value.
+        key: Json<'a>,
+    },
+}
+
+impl<'a> Json<'a> {
+    /// Asserts that the node is an array whose elements all satisfy the predicate.
+    ///
+    /// Returns the elements on success so the validated array can be used directly.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("[true, false, null]").unwrap();
+    /// let is_bool = |e: &Json| matches!(e, Json::True { .. } | Json::False { .. });
+    /// assert_eq!(
+    ///     Err(ValidationError::InvalidElement { index: 2 }),
+    ///     parsed.array_of(is_bool)
+    /// );
+    /// ```
+    pub fn array_of<FN>(&self, pred: FN) -> Result<&[Json<'a>], ValidationError<'a>>
+    where
+        FN: Fn(&Json<'a>) -> bool,
+    {
+        match self {
+            Self::Array { elem, .. } => match elem.iter().position(|e| !pred(e)) {
+                Some(index) => Err(ValidationError::InvalidElement { index }),
+                None => Ok(elem),
+            },
+            _ => Err(ValidationError::WrongVariant { expected: "array" }),
+        }
+    }
+
+    /// Asserts that the node is an object whose values all satisfy the predicate.
+    ///
+    /// Returns the key value pairs on success so the validated object can be used directly.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("{\"a\": 1, \"b\": \"two\"}").unwrap();
+    /// let is_number = |e: &Json| matches!(e, Json::Number { .. });
+    /// assert_eq!(
+    ///     Err(ValidationError::InvalidValue { key: Json::String { elem: "b" } }),
+    ///     parsed.object_of(is_number)
+    /// );
+    /// ```
+    pub fn object_of<FN>(&self, pred: FN) -> Result<&[(Json<'a>, Json<'a>)], ValidationError<'a>>
+    where
+        FN: Fn(&Json<'a>) -> bool,
+    {
+        match self {
+            Self::Object { elem, .. } => match elem.iter().find(|(_, v)| !pred(v)) {
+                Some((key, _)) => Err(ValidationError::InvalidValue { key: key.clone() }),
+                None => Ok(elem),
+            },
+            _ => Err(ValidationError::WrongVariant { expected: "object" }),
+        }
+    }
+
+    /// Converts an array into a vector by applying the extractor to every element.
+    ///
+    /// Stops at the first element the extractor fails on, the error then contains the index of
+    /// that element.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("[\"a\", \"b\"]").unwrap();
+    /// let as_str = |e: &Json| match e {
+    ///     Json::String { elem } => Ok(elem.to_string()),
+    ///     _ => Err("not a string".to_string()),
+    /// };
+    /// assert_eq!(Ok(vec!("a".to_string(), "b".to_string())), parsed.try_into_vec(as_str));
+    /// ```
+    pub fn try_into_vec<T, FN>(&self, f: FN) -> Result<Vec<T>, String>
+    where
+        FN: Fn(&Json<'a>) -> Result<T, String>,
+    {
+        match self {
+            Self::Array { elem, .. } => elem
+                .iter()
+                .enumerate()
+                .map(|(idx, e)| f(e).map_err(|err| format!("Element {}: {}", idx, err)))
+                .collect(),
+            _ => Err("Expected an array".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_string(e: &Json) -> bool {
+        matches!(e, Json::String { .. })
+    }
+
+    #[test]
+    fn test_array_of_uniform() {
+        let parsed = json("[\"a\", \"b\", \"c\"]").unwrap();
+        assert_eq!(Ok(3), parsed.array_of(is_string).map(|e| e.len()));
+    }
+
+    #[test]
+    fn test_array_of_mixed() {
+        let parsed = json("[\"a\", \"b\", 3, \"d\", 5]").unwrap();
+        assert_eq!(
+            Err(ValidationError::InvalidElement { index: 2 }),
+            parsed.array_of(is_string)
+        );
+    }
+
+    #[test]
+    fn test_object_of() {
+        let parsed = json("{\"a\": \"x\", \"b\": \"y\"}").unwrap();
+        assert!(parsed.object_of(is_string).is_ok());
+
+        let parsed = json("{\"a\": \"x\", \"b\": true}").unwrap();
+        assert_eq!(
+            Err(ValidationError::InvalidValue {
+                key: Json::String { elem: "b" }
+            }),
+            parsed.object_of(is_string)
+        );
+    }
+
+    #[test]
+    fn test_wrong_variant() {
+        let parsed = json("{\"a\": \"x\"}").unwrap();
+        assert_eq!(
+            Err(ValidationError::WrongVariant { expected: "array" }),
+            parsed.array_of(is_string)
+        );
+
+        let parsed = json("[\"a\"]").unwrap();
+        assert_eq!(
+            Err(ValidationError::WrongVariant { expected: "object" }),
+            parsed.object_of(is_string)
+        );
+    }
+
+    fn as_i64(e: &Json) -> Result<i64, String> {
+        match e {
+            Json::Number { elem } => elem
+                .parse()
+                .map_err(|_| format!("'{}' is not an i64", elem)),
+            _ => Err("Expected a number".into()),
+        }
+    }
+
+    #[test]
+    fn test_try_into_vec() {
+        let parsed = json("[1, 2, 3]").unwrap();
+        assert_eq!(Ok(vec!(1, 2, 3)), parsed.try_into_vec(as_i64));
+
+        let parsed = json("[]").unwrap();
+        assert_eq!(Ok(vec!()), parsed.try_into_vec(as_i64));
+    }
+
+    #[test]
+    fn test_try_into_vec_error() {
+        let parsed = json("[1, \"x\", 3]").unwrap();
+        assert_eq!(
+            Err("Element 1: Expected a number".to_string()),
+            parsed.try_into_vec(as_i64)
+        );
+
+        let parsed = json("{\"a\": 1}").unwrap();
+        assert_eq!(
+            Err("Expected an array".to_string()),
+            parsed.try_into_vec(as_i64)
+        );
+    }
+}
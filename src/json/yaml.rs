@@ -0,0 +1,134 @@
+use super::*;
+
+/// Whether a string can be emitted as a plain YAML scalar without changing its meaning.
+fn is_plain(text: &str) -> bool {
+    const INDICATORS: &[char] = &[
+        '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@',
+        '`',
+    ];
+    const RESERVED: &[&str] = &["true", "false", "null", "yes", "no", "on", "off", "~"];
+
+    let starts_with_indicator = text.starts_with(INDICATORS);
+    let is_reserved = RESERVED.iter().any(|r| r.eq_ignore_ascii_case(text));
+    let looks_numeric = text.parse::<f64>().is_ok() || text.starts_with('.');
+
+    !text.is_empty()
+        && text.trim() == text
+        && !starts_with_indicator
+        && !is_reserved
+        && !looks_numeric
+        && !text.contains(['\\', '\t'])
+        && !text.contains(": ")
+        && !text.contains(" #")
+        && !text.ends_with(':')
+}
+
+/// Inline representation of terminals and empty containers.
+fn scalar(value: &Json) -> String {
+    match value {
+        Json::Object { .. } => "{}".to_string(),
+        Json::Array { .. } => "[]".to_string(),
+        Json::String { elem } if is_plain(elem) => elem.to_string(),
+        // Json escape sequences are valid in YAML double quoted scalars
+        Json::String { elem } => format!("\"{}\"", elem),
+        Json::Number { elem }
+        | Json::True { elem }
+        | Json::False { elem }
+        | Json::Null { elem }
+        | Json::Error { elem } => elem.to_string(),
+    }
+}
+
+/// Lines of the block representation of a value, without indentation.
+fn lines(value: &Json) -> Vec<String> {
+    let mut res = Vec::new();
+
+    match value {
+        Json::Object { elem, .. } if !elem.is_empty() => {
+            for (key, value) in elem {
+                match value.entry_count() {
+                    0 => res.push(format!("{}: {}", scalar(key), scalar(value))),
+                    _ => {
+                        res.push(format!("{}:", scalar(key)));
+                        res.extend(lines(value).iter().map(|l| format!("  {}", l)));
+                    }
+                }
+            }
+        }
+        Json::Array { elem, .. } if !elem.is_empty() => {
+            for value in elem {
+                for (idx, line) in lines(value).iter().enumerate() {
+                    match idx {
+                        0 => res.push(format!("- {}", line)),
+                        _ => res.push(format!("  {}", line)),
+                    }
+                }
+            }
+        }
+        _ => res.push(scalar(value)),
+    }
+    res
+}
+
+impl<'a> Json<'a> {
+    /// Serializes the Json as block style YAML.
+    ///
+    /// Only the subset of YAML that Json maps to is emitted: indented mappings and sequences,
+    /// and scalars. Strings are left plain when unambiguous and double quoted otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::json::*;
+    ///
+    /// let parsed = json("{\"name\": \"bob\", \"tags\": [\"a\", \"b: c\"]}").unwrap();
+    /// assert_eq!("name: bob\ntags:\n  - a\n  - \"b: c\"\n", parsed.to_yaml_string());
+    /// ```
+    ///
+    /// # Note
+    /// Only available with the `yaml` feature.
+    pub fn to_yaml_string(&self) -> String {
+        lines(self).iter().map(|l| format!("{}\n", l)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_yaml_string_nested_object() {
+        let parsed = json(
+            "{\"server\": {\"host\": \"localhost\", \"port\": 8080, \"tls\": false}, \
+            \"users\": [], \"limits\": {}, \"owner\": null}",
+        )
+        .unwrap();
+        assert_eq!(
+            "server:\n  host: localhost\n  port: 8080\n  tls: false\n\
+            users: []\nlimits: {}\nowner: null\n",
+            parsed.to_yaml_string()
+        );
+    }
+
+    #[test]
+    fn test_to_yaml_string_array() {
+        let parsed = json("[1, [2, 3], {\"a\": 1, \"b\": [true]}, \"x\"]").unwrap();
+        assert_eq!(
+            "- 1\n- - 2\n  - 3\n- a: 1\n  b:\n    - true\n- x\n",
+            parsed.to_yaml_string()
+        );
+    }
+
+    #[test]
+    fn test_to_yaml_string_quoting() {
+        let parsed = json(
+            "[\"true\", \"12\", \"\", \" padded\", \"- item\", \"a #comment\", \
+            \"key: value\", \"line\\nbreak\", \"plain text\"]",
+        )
+        .unwrap();
+        assert_eq!(
+            "- \"true\"\n- \"12\"\n- \"\"\n- \" padded\"\n- \"- item\"\n- \"a #comment\"\n\
+            - \"key: value\"\n- \"line\\nbreak\"\n- plain text\n",
+            parsed.to_yaml_string()
+        );
+    }
+}
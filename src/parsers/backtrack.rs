@@ -0,0 +1,185 @@
+use crate::parsers::*;
+
+/// Error wrapper telling alternative combinators whether they may try their next branch.
+///
+/// `or` always tries its second parser when the first one fails. `or_backtrack` only does so
+/// when the error of the first parser is `Recoverable`, a `Fatal` error aborts the whole parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backtrack<E> {
+    /// The next alternative may be attempted.
+    Recoverable(E),
+
+    /// The parse must be aborted.
+    Fatal(E),
+}
+
+impl<E> Backtrack<E> {
+    /// Whether the error aborts the parse.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::Fatal(_))
+    }
+
+    /// Retrieves the wrapped error.
+    pub fn into_inner(self) -> E {
+        match self {
+            Self::Recoverable(err) | Self::Fatal(err) => err,
+        }
+    }
+}
+
+/// Classifies the errors of a parser as recoverable or fatal.
+///
+/// Errors for which the predicate returns true are recoverable, the others are fatal.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = backtrack_on(literal("ab"), |err: &String| err.contains("'ab'"));
+/// assert_eq!(Ok(("", "ab")), parser.parse("ab"));
+/// assert!(!parser.parse("1").unwrap_err().is_fatal());
+///
+/// let parser = backtrack_on(literal::<_, String>("ab"), |_| false);
+/// assert!(parser.parse("1").unwrap_err().is_fatal());
+/// ```
+pub const fn backtrack_on<P, I, R, O, E, FN>(
+    parser: P,
+    pred: FN,
+) -> impl Parser<I, R, O, Backtrack<E>>
+where
+    P: Parser<I, R, O, E>,
+    FN: Fn(&E) -> bool,
+{
+    move |input: I| {
+        parser.parse(input).map_err(|err| match pred(&err) {
+            true => Backtrack::Recoverable(err),
+            false => Backtrack::Fatal(err),
+        })
+    }
+}
+
+/// Takes two parsers and returns which ever result matches first, unless the first fails fatally.
+///
+/// # Result Conditions
+/// Tries the first parser, then the second one only if the first error is recoverable.
+/// Otherwise behaves like `or`.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let recoverable = backtrack_on(literal("--"), |_| true);
+/// let unrecoverable = backtrack_on(literal("-"), |_| false);
+///
+/// let parser = or_backtrack(recoverable, backtrack_on(identifier, |_| true));
+/// assert_eq!(Ok(("", "ident")), parser.parse("ident"));
+///
+/// let parser = or_backtrack(unrecoverable, backtrack_on(identifier, |_| true));
+/// assert!(parser.parse("ident").is_err());
+/// ```
+pub const fn or_backtrack<PA, PB, I, R, O, E>(pa: PA, pb: PB) -> impl Parser<I, R, O, Backtrack<E>>
+where
+    PA: Parser<I, R, O, Backtrack<E>>,
+    PB: Parser<I, R, O, Backtrack<E>>,
+    I: Clone,
+{
+    move |input: I| match pa.parse(input.clone()) {
+        Ok(r) => Ok(r),
+        Err(Backtrack::Fatal(err)) => Err(Backtrack::Fatal(err)),
+        Err(Backtrack::Recoverable(_)) => pb.parse(input),
+    }
+}
+
+/// Commits to a parser: any of its errors becomes fatal.
+///
+/// Meant to wrap what follows the point at which an alternative is known to be the right one,
+/// e.g. the content of an object once its opening brace is parsed. `cut` flags the error by
+/// turning it into a [`Backtrack::Fatal`], which `or_backtrack` detects to propagate it instead
+/// of trying its next alternative.
+///
+/// # Result Conditions
+/// Same as input parser, errors are all fatal.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let start = backtrack_on(literal("{"), |_| true);
+/// let body = backtrack_on(identifier, |_| true);
+/// let block = and(start, cut(body));
+/// assert!(!block.parse("[").unwrap_err().is_fatal());
+/// assert!(block.parse("{1").unwrap_err().is_fatal());
+///
+/// // The fallback would match, but a `{` commits to a block
+/// let fallback = backtrack_on(and(literal("{"), literal("1")), |_| true);
+/// let parser = or_backtrack(block, fallback);
+/// assert!(parser.parse("{1").unwrap_err().is_fatal());
+/// ```
+pub const fn cut<P, I, R, O, E>(parser: P) -> impl Parser<I, R, O, Backtrack<E>>
+where
+    P: Parser<I, R, O, Backtrack<E>>,
+{
+    move |input: I| {
+        parser
+            .parse(input)
+            .map_err(|err| Backtrack::Fatal(err.into_inner()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backtrack_on_error_message() {
+        // Failing on the opening brace means nothing was committed to yet
+        let opening_failed = |err: &String| err == "Could not parse '{'";
+        let block = backtrack_on(and(literal("{"), identifier), opening_failed);
+        let fallback = backtrack_on(and(literal("{"), whitespace), |_| true);
+
+        let parser = or_backtrack(block, fallback);
+        assert_eq!(Ok(("", ("{", "ident"))), parser.parse("{ident"));
+        assert_eq!(
+            Err(Backtrack::Recoverable("Could not parse '{'".to_string())),
+            parser.parse("[ident")
+        );
+
+        // The fallback would match but is never attempted
+        assert_eq!(
+            Err(Backtrack::Fatal(
+                "Could not parse '\\A[a-zA-Z_][a-zA-Z0-9_]*'".to_string()
+            )),
+            parser.parse("{ ")
+        );
+    }
+
+    #[test]
+    fn test_backtrack_into_inner() {
+        assert_eq!("a", Backtrack::Fatal("a").into_inner());
+        assert_eq!("b", Backtrack::Recoverable("b").into_inner());
+        assert!(!Backtrack::Recoverable("b").is_fatal());
+    }
+
+    #[test]
+    fn test_cut_commits_to_alternative() {
+        // `key = value` or a bare `key`, committing once the `=` is seen
+        let assignment = and(
+            backtrack_on(left(identifier, literal("=")), |_| true),
+            cut(backtrack_on(identifier, |_| true)),
+        );
+        let bare = map(backtrack_on(identifier, |_| true), |key| (key, ""));
+        let parser = or_backtrack(assignment, bare);
+
+        assert_eq!(Ok(("", ("a", "b"))), parser.parse("a=b"));
+        assert_eq!(Ok((" b", ("a", ""))), parser.parse("a b"));
+        assert_eq!(
+            Err(Backtrack::Fatal(
+                "Could not parse '\\A[a-zA-Z_][a-zA-Z0-9_]*'".to_string()
+            )),
+            parser.parse("a=1")
+        );
+    }
+}
@@ -5,6 +5,9 @@ use regex::Regex;
 
 /// A parser that that succeed if the given regex matches the input.
 ///
+/// The match must start at the beginning of the input, matches found further in are rejected
+/// even if the regex is not anchored with `\A`.
+///
 /// # Example
 /// ```
 /// use parser_combinator::parsers::*;
@@ -12,47 +15,86 @@ use regex::Regex;
 ///
 /// let parser = matching(&JSON_NUMBER_REGEX);
 /// assert_eq!(Ok(("", "-2.4")), parser.parse("-2.4"));
-/// assert!(parser.parse("NaN").is_err());
+///
+/// let err: ParseError = parser.parse("NaN").unwrap_err();
+/// assert_eq!(0, err.offset);
 /// ```
-pub const fn matching<'a, 'b>(
+///
+/// # Note
+/// The error can be any type built from a [`ParseError`], `String` included. Its offset is the
+/// one of the input the parser failed on, which gets located in the source with [`located`].
+pub const fn matching<'a, 'b, E>(
     expected: &Regex,
-) -> impl Parser<&'a str, &'b str, &'b str, String> + '_
+) -> impl Parser<&'a str, &'b str, &'b str, E> + '_
 where
     'a: 'b,
+    E: From<ParseError>,
 {
-    move |input: &'a str| match expected.find(&input[..]) {
-        Some(matched) => Ok((
-            &input[matched.end()..],
-            &input[matched.start()..matched.end()],
-        )),
+    move |input: &'a str| match expected.find(input) {
+        Some(matched) if matched.start() == 0 => {
+            Ok((&input[matched.end()..], &input[0..matched.end()]))
+        }
 
-        None => Err(format!("Could not parse '{}'", expected.as_str())),
+        _ => {
+            let message = format!("Could not parse '{}'", expected.as_str());
+            Err(ParseError::at(message, input, input).into())
+        }
     }
 }
 
-/// Matches exactly the given word but insensitive to case.
+/// Matches exactly the given word.
 ///
 /// # Example
 /// ```
 /// use parser_combinator::parsers::*;
 ///
-/// let parser = literal(" foo");
+/// let parser = literal::<_, String>(" foo");
 /// assert_eq!(Ok(("", " foo")), parser.parse(" foo"));
 /// assert!(parser.parse("foo").is_err());
+/// assert!(parser.parse(" FOO").is_err());
 /// ```
-pub const fn literal<'a, 'b, A>(expected: A) -> impl Parser<&'a str, &'b str, &'b str, String>
+///
+/// # Note
+/// Like [`matching`], fails with any error built from a [`ParseError`].
+pub const fn literal<'a, 'b, A, E>(expected: A) -> impl Parser<&'a str, &'b str, &'b str, E>
 where
     A: AsRef<str>,
     'a: 'b,
+    E: From<ParseError>,
 {
     move |input: &'a str| {
         let expected = expected.as_ref();
-        if input.len() < expected.len() {
-            return Err(format!("Could not parse '{}'", expected));
+        match input.get(0..expected.len()) {
+            Some(ex) if ex == expected => Ok((&input[expected.len()..], ex)),
+
+            _ => {
+                let message = format!("Could not parse '{}'", expected);
+                Err(ParseError::at(message, input, input).into())
+            }
         }
-        match &input[0..expected.len()].to_lowercase() {
-            ex if ex == &expected.to_lowercase() => {
-                Ok((&input[expected.len()..], &input[0..expected.len()]))
+    }
+}
+
+/// Matches exactly the given word but insensitive to case.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = literal_ci("foo");
+/// assert_eq!(Ok(("", "FoO")), parser.parse("FoO"));
+/// assert!(parser.parse("bar").is_err());
+/// ```
+pub const fn literal_ci<'a, 'b, A>(expected: A) -> impl Parser<&'a str, &'b str, &'b str, String>
+where
+    A: AsRef<str>,
+    'a: 'b,
+{
+    move |input: &'a str| {
+        let expected = expected.as_ref();
+        match input.get(0..expected.len()) {
+            Some(ex) if ex.to_lowercase() == expected.to_lowercase() => {
+                Ok((&input[expected.len()..], ex))
             }
 
             _ => Err(format!("Could not parse '{}'", expected)),
@@ -60,6 +102,150 @@ where
     }
 }
 
+/// Consumes the input up to the first occurrence of the needle, which is left in the remainder.
+///
+/// An empty needle matches right away, yielding an empty slice.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = take_until("\n");
+/// assert_eq!(Ok(("\nsecond", "first line")), parser.parse("first line\nsecond"));
+/// assert_eq!(Err("Could not find '\n'".to_string()), parser.parse("no newline"));
+/// ```
+pub const fn take_until<'a, A>(needle: A) -> impl Parser<&'a str, &'a str, &'a str, String>
+where
+    A: AsRef<str>,
+{
+    move |input: &'a str| {
+        let needle = needle.as_ref();
+        match input.find(needle) {
+            Some(idx) => Ok((&input[idx..], &input[..idx])),
+            None => Err(format!("Could not find '{}'", needle)),
+        }
+    }
+}
+
+/// Consumes the longest prefix of characters satisfying the predicate. Always succeeds.
+///
+/// Faster than `matching` for simple character classes, as no regex is involved.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = take_while(|c| c.is_ascii_digit());
+/// assert_eq!(Ok(("abc", "123")), parser.parse("123abc"));
+/// assert_eq!(Ok(("abc", "")), parser.parse("abc"));
+/// ```
+pub const fn take_while<'a, FN>(pred: FN) -> impl Parser<&'a str, &'a str, &'a str, String>
+where
+    FN: Fn(char) -> bool,
+{
+    move |input: &'a str| {
+        let end = input
+            .char_indices()
+            .find(|(_, c)| !pred(*c))
+            .map_or(input.len(), |(idx, _)| idx);
+        Ok((&input[end..], &input[..end]))
+    }
+}
+
+/// Consumes the longest non-empty prefix of characters satisfying the predicate.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = take_while1(|c| c.is_alphabetic());
+/// assert_eq!(Ok(("1", "été")), parser.parse("été1"));
+/// assert!(parser.parse("1").is_err());
+/// ```
+pub const fn take_while1<'a, FN>(pred: FN) -> impl Parser<&'a str, &'a str, &'a str, String>
+where
+    FN: Fn(char) -> bool,
+{
+    let parser = take_while(pred);
+
+    move |input: &'a str| match parser.parse(input)? {
+        (_, "") => Err("Expected at least one matching character".to_string()),
+        res => Ok(res),
+    }
+}
+
+/// Consumes a single character satisfying the predicate.
+///
+/// # Result Conditions
+/// Fails on empty input, or when the first character does not satisfy the predicate.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = satisfy(|c| c.is_alphabetic());
+/// assert_eq!(Ok(("b1", 'a')), parser.parse("ab1"));
+/// assert!(parser.parse("1").is_err());
+/// ```
+pub const fn satisfy<'a, FN>(pred: FN) -> impl Parser<&'a str, &'a str, char, String>
+where
+    FN: Fn(char) -> bool,
+{
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if pred(c) => Ok((&input[c.len_utf8()..], c)),
+        Some(c) => Err(format!("Unexpected character '{}'", c)),
+        None => Err("Expected a character, found the end of the input".to_string()),
+    }
+}
+
+/// Consumes a single character appearing in the given set.
+///
+/// # Result Conditions
+/// Fails on empty input, or when the first character is not in the set.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// assert_eq!(Ok(("5", '-')), one_of("+-").parse("-5"));
+/// assert!(one_of("+-").parse("5").is_err());
+/// ```
+pub const fn one_of<'a, 's>(
+    set: &'s str,
+) -> impl Parser<&'a str, &'a str, char, String> + use<'a, 's> {
+    let parser = satisfy(move |c| set.contains(c));
+
+    move |input: &'a str| {
+        parser
+            .parse(input)
+            .map_err(|_| format!("Expected one of '{}'", set))
+    }
+}
+
+/// Consumes a single character not appearing in the given set.
+///
+/// # Result Conditions
+/// Fails on empty input, or when the first character is in the set.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// assert_eq!(Ok(("\"", 'a')), none_of("\"\\").parse("a\""));
+/// assert!(none_of("\"\\").parse("\"a").is_err());
+/// ```
+pub const fn none_of<'a, 's>(
+    set: &'s str,
+) -> impl Parser<&'a str, &'a str, char, String> + use<'a, 's> {
+    let parser = satisfy(move |c| !set.contains(c));
+
+    move |input: &'a str| {
+        parser
+            .parse(input)
+            .map_err(|_| format!("Expected none of '{}'", set))
+    }
+}
+
 /// Parse an identifier, to most programming languages sense.
 /// # Example
 /// ```
@@ -143,9 +329,133 @@ pub fn not_newline<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, &'b str), Str
     matching(&NOT_NEWLINE_REGEX).parse(input)
 }
 
+/// Parse a `//` line comment, up to the newline, or a `/* */` block comment.
+///
+/// # Result Conditions
+/// Fails if a block comment is unterminated or contains another block comment, as nesting is
+/// not supported.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// assert_eq!(Ok(("\n1", "// one")), comment.parse("// one\n1"));
+/// assert_eq!(Ok((" 2", "/* two */")), comment.parse("/* two */ 2"));
+/// assert!(comment.parse("/* three").is_err());
+/// ```
+pub fn comment<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, &'b str), String> {
+    if input.starts_with("//") {
+        let end = input.find(['\n', '\r']).unwrap_or(input.len());
+        return Ok((&input[end..], &input[..end]));
+    }
+
+    let body = input
+        .strip_prefix("/*")
+        .ok_or_else(|| "Expected a comment".to_string())?;
+    let end = body
+        .find("*/")
+        .ok_or_else(|| "Unterminated block comment".to_string())?;
+    if body[..end].contains("/*") {
+        return Err("Nested block comments are not allowed".to_string());
+    }
+
+    let end = end + "/**/".len();
+    Ok((&input[end..], &input[..end]))
+}
+
+/// Succeeds only at the end of the input.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// assert_eq!(Ok(("", ())), eof.parse(""));
+/// assert!(eof.parse("x").is_err());
+/// assert_eq!(Ok(("", "foo")), left(identifier, eof).parse("foo"));
+/// ```
+pub fn eof<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, ()), String> {
+    match input.is_empty() {
+        true => Ok((input, ())),
+        false => Err("Expected end of input".to_string()),
+    }
+}
+
+/// Consumes the indentation of a line and returns its width. Always succeeds.
+///
+/// Spaces and tabs both count as one column.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// assert_eq!(Ok(("foo", 3)), indent_level.parse(" \t foo"));
+/// assert_eq!(Ok(("foo", 0)), indent_level.parse("foo"));
+/// ```
+pub fn indent_level<'b, 'a: 'b>(input: &'a str) -> Result<(&'b str, usize), String> {
+    let width = input.bytes().take_while(|b| matches!(b, b' ' | b'\t')).count();
+    Ok((&input[width..], width))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn test_satisfy() {
+        let digit = satisfy(|c| c.is_ascii_digit());
+        assert_eq!(Ok(("23", '1')), digit.parse("123"));
+        assert_eq!(
+            Err("Unexpected character 'a'".to_string()),
+            digit.parse("a1")
+        );
+        assert_eq!(
+            Err("Expected a character, found the end of the input".to_string()),
+            digit.parse("")
+        );
+    }
+
+    #[test]
+    fn test_satisfy_multibyte() {
+        let parser = satisfy(|c| !c.is_ascii());
+        assert_eq!(Ok(("té", 'é')), parser.parse("été"));
+        assert_eq!(Ok(("!", '🦀')), parser.parse("🦀!"));
+        assert_eq!(
+            Err("Unexpected character 't'".to_string()),
+            parser.parse("té")
+        );
+
+        let letters = while_(satisfy(char::is_alphabetic));
+        assert_eq!(Ok(("1", vec!('ü', 'b', 'é', 'r'))), letters.parse("übér1"));
+    }
+
+    #[test]
+    fn test_one_of_none_of() {
+        assert_eq!(Ok(("b", 'é')), one_of("aé").parse("éb"));
+        assert_eq!(
+            Err("Expected one of 'aé'".to_string()),
+            one_of("aé").parse("e")
+        );
+        assert!(one_of("aé").parse("").is_err());
+        assert!(one_of("").parse("a").is_err());
+
+        assert_eq!(Ok(("é", '🦀')), none_of("aé").parse("🦀é"));
+        assert_eq!(
+            Err("Expected none of 'aé'".to_string()),
+            none_of("aé").parse("é")
+        );
+        assert!(none_of("aé").parse("").is_err());
+        assert_eq!(Ok(("", 'a')), none_of("").parse("a"));
+    }
+
+    #[test]
+    fn test_none_of_string_content() {
+        // Unescaped content of a string, like `string_content` without escapes
+        let content = map(while_(none_of("\"\\")), |chars| chars.into_iter().collect());
+        assert_eq!(
+            Ok(("\" rest", "ünï cödé".to_string())),
+            content.parse("ünï cödé\" rest")
+        );
+    }
+
     #[test]
     fn test_whitespace_no_newline() {
         let result = whitespace_no_newline.parse(" \t\r\n");
@@ -171,4 +481,91 @@ mod tests {
     fn test_newline_2() {
         assert_eq!(newline.parse("\n\t"), Ok(("\t", "\n")));
     }
+
+    #[test]
+    fn test_literal_case() {
+        assert!(literal::<_, String>("foo").parse("FOO").is_err());
+        assert_eq!(Ok(("", "FOO")), literal_ci("foo").parse("FOO"));
+    }
+
+    #[test]
+    fn test_literal_multibyte() {
+        let error = Err("Could not parse 'ab'".to_string());
+        assert_eq!(error, literal("ab").parse("é"));
+        assert_eq!(error, literal_ci("ab").parse("é"));
+        assert_eq!(error, literal("ab").parse("a🦀"));
+        assert!(literal::<_, String>("caf").parse("café").is_ok());
+        assert!(literal::<_, String>("cafe").parse("café").is_err());
+        assert_eq!(Ok(("", "CAFÉ")), literal_ci("café").parse("CAFÉ"));
+    }
+
+    #[test]
+    fn test_matching_unanchored() {
+        let digits = Regex::new("[0-9]+").unwrap();
+        assert_eq!(Ok(("abc", "12")), matching::<String>(&digits).parse("12abc"));
+        assert_eq!(
+            Err("Could not parse '[0-9]+'".to_string()),
+            matching(&digits).parse("abc12")
+        );
+    }
+
+    #[test]
+    fn test_comment() {
+        assert_eq!(Ok(("", "// to the end")), comment.parse("// to the end"));
+        assert_eq!(Ok(("\r\nx", "//")), comment.parse("//\r\nx"));
+        assert_eq!(Ok(("x", "/* a\n * b */")), comment.parse("/* a\n * b */x"));
+        assert_eq!(Ok(("", "/**/")), comment.parse("/**/"));
+        assert_eq!(
+            Err("Expected a comment".to_string()),
+            comment.parse("/ not a comment")
+        );
+    }
+
+    #[test]
+    fn test_comment_malformed_block() {
+        assert_eq!(
+            Err("Unterminated block comment".to_string()),
+            comment.parse("/* open")
+        );
+        assert_eq!(
+            Err("Nested block comments are not allowed".to_string()),
+            comment.parse("/* outer /* inner */ */")
+        );
+    }
+
+    #[test]
+    fn test_eof() {
+        assert_eq!(Ok(("", ())), eof.parse(""));
+        assert_eq!(Err("Expected end of input".to_string()), eof.parse(" "));
+        assert!(left(identifier, eof).parse("foo bar").is_err());
+    }
+
+    #[test]
+    fn test_take_while() {
+        let parser = take_while(char::is_whitespace);
+        assert_eq!(Ok(("a", " \t\n")), parser.parse(" \t\na"));
+        assert_eq!(Ok(("", "  ")), parser.parse("  "));
+        assert_eq!(Ok(("", "")), parser.parse(""));
+    }
+
+    #[test]
+    fn test_take_while1() {
+        let parser = take_while1(|c| c == 'a');
+        assert_eq!(Ok(("b", "aa")), parser.parse("aab"));
+        assert_eq!(
+            Err("Expected at least one matching character".to_string()),
+            parser.parse("baa")
+        );
+        assert!(parser.parse("").is_err());
+    }
+
+    #[test]
+    fn test_take_until() {
+        let parser = take_until("*/");
+        assert_eq!(Ok(("*/ b */", " a ")), parser.parse(" a */ b */"));
+        assert_eq!(Ok(("*/", "")), parser.parse("*/"));
+        assert_eq!(Err("Could not find '*/'".to_string()), parser.parse("a * /"));
+
+        assert_eq!(Ok(("abc", "")), take_until("").parse("abc"));
+    }
 }
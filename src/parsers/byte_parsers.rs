@@ -0,0 +1,95 @@
+use crate::parsers::*;
+
+/// Matches exactly the given bytes.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = byte_literal(b"\x89PNG");
+/// assert_eq!(Ok((&b"\r\n"[..], &b"\x89PNG"[..])), parser.parse(b"\x89PNG\r\n"));
+/// assert!(parser.parse(b"GIF8").is_err());
+/// ```
+pub const fn byte_literal<'a, A>(expected: A) -> impl Parser<&'a [u8], &'a [u8], &'a [u8], String>
+where
+    A: AsRef<[u8]>,
+{
+    move |input: &'a [u8]| {
+        let expected = expected.as_ref();
+        match input.strip_prefix(expected) {
+            Some(rem) => Ok((rem, &input[..expected.len()])),
+            None => Err(format!("Could not parse bytes {:?}", expected)),
+        }
+    }
+}
+
+/// Consumes exactly `n` bytes.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// assert_eq!(Ok((&b"c"[..], &b"ab"[..])), take_bytes(2).parse(b"abc"));
+/// assert!(take_bytes(4).parse(b"abc").is_err());
+/// ```
+pub const fn take_bytes<'a>(n: usize) -> impl Parser<&'a [u8], &'a [u8], &'a [u8], String> {
+    move |input: &'a [u8]| match input.split_at_checked(n) {
+        Some((taken, rem)) => Ok((rem, taken)),
+        None => Err(format!("Expected {} bytes, found {}", n, input.len())),
+    }
+}
+
+/// Consumes the longest prefix of bytes satisfying the predicate. Always succeeds.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = byte_while(|b| b.is_ascii_digit());
+/// assert_eq!(Ok((&b"abc"[..], &b"123"[..])), parser.parse(b"123abc"));
+/// assert_eq!(Ok((&b"abc"[..], &b""[..])), parser.parse(b"abc"));
+/// ```
+pub const fn byte_while<'a, FN>(pred: FN) -> impl Parser<&'a [u8], &'a [u8], &'a [u8], String>
+where
+    FN: Fn(u8) -> bool,
+{
+    move |input: &'a [u8]| {
+        let end = input.iter().position(|b| !pred(*b)).unwrap_or(input.len());
+        Ok((&input[end..], &input[..end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Record made of a tag, a one byte length, and that many bytes of payload.
+    fn record(input: &[u8]) -> Result<(&[u8], &[u8]), String> {
+        let length = map(take_bytes(1), |len: &[u8]| len[0] as usize);
+        let parser = right(byte_literal(b"REC"), and_then(length, take_bytes));
+        parser.parse(input)
+    }
+
+    #[test]
+    fn test_length_prefixed_record() {
+        assert_eq!(Ok((&b"rest"[..], &b"abc"[..])), record(b"REC\x03abcrest"));
+        assert_eq!(Ok((&b""[..], &b""[..])), record(b"REC\x00"));
+        assert_eq!(
+            Err("Expected 3 bytes, found 2".to_string()),
+            record(b"REC\x03ab")
+        );
+        assert!(record(b"RAW\x01a").is_err());
+    }
+
+    #[test]
+    fn test_records_with_combinators() {
+        let header = and(byte_literal([0xCA, 0xFE]), byte_while(|b| b == 0));
+        let parser = and(header, while_(record));
+
+        let input = b"\xCA\xFE\x00\x00REC\x01aREC\x02bc\xFF";
+        let (rem, ((magic, padding), records)) = parser.parse(input).unwrap();
+        assert_eq!((&[0xCA, 0xFE][..], &[0, 0][..]), (magic, padding));
+        assert_eq!(vec!(&b"a"[..], &b"bc"[..]), records);
+        assert_eq!(&b"\xFF"[..], rem);
+    }
+}
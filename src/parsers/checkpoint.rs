@@ -0,0 +1,132 @@
+use crate::parsers::*;
+
+/// Resumable parser state: the remainder left by a parse, and where it starts in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint<'a> {
+    source: &'a str,
+    remainder: &'a str,
+}
+
+impl<'a> Checkpoint<'a> {
+    /// Creates a checkpoint at the start of the source.
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            remainder: source,
+        }
+    }
+
+    /// Input yet to be parsed.
+    pub fn remainder(&self) -> &'a str {
+        self.remainder
+    }
+
+    /// Offset of the remainder in the source, in bytes.
+    ///
+    /// Returns None if the parse went through input that is not a slice of the source.
+    pub fn offset(&self) -> Option<usize> {
+        self.remainder.source_range_start(self.source)
+    }
+
+    /// Whether the whole source was parsed.
+    pub fn is_done(&self) -> bool {
+        self.remainder.is_empty()
+    }
+
+    /// Parses from the checkpoint and returns the checkpoint after the parse with its output.
+    ///
+    /// The checkpoint itself is left untouched, so the same state can be resumed from again.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::parsers::*;
+    ///
+    /// let parser = left(identifier, maybe(whitespace));
+    /// let start = Checkpoint::new("foo bar");
+    /// let (next, foo) = start.resume(&parser).unwrap();
+    /// assert_eq!(("foo", Some(4)), (foo, next.offset()));
+    ///
+    /// let (last, bar) = next.resume(&parser).unwrap();
+    /// assert_eq!("bar", bar);
+    /// assert!(last.is_done());
+    /// ```
+    pub fn resume<P, O, E>(&self, parser: &P) -> Result<(Checkpoint<'a>, O), E>
+    where
+        P: Parser<&'a str, &'a str, O, E>,
+    {
+        let (remainder, out) = parser.parse(self.remainder)?;
+        let next = Self {
+            source: self.source,
+            remainder,
+        };
+        Ok((next, out))
+    }
+}
+
+/// Applies the parser and returns a checkpoint after it alongside its output.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let source = "key value";
+/// let parser = checkpoint(left(identifier, whitespace), source);
+/// let (_, (key, state)) = parser.parse(source).unwrap();
+/// assert_eq!(("key", Some(4)), (key, state.offset()));
+/// assert_eq!("value", state.remainder());
+/// ```
+pub const fn checkpoint<'a, P, O, E>(
+    parser: P,
+    source: &'a str,
+) -> impl Parser<&'a str, &'a str, (O, Checkpoint<'a>), E>
+where
+    P: Parser<&'a str, &'a str, O, E>,
+{
+    move |input: &'a str| {
+        let (remainder, out) = parser.parse(input)?;
+        Ok((remainder, (out, Checkpoint { source, remainder })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regexes::JSON_NUMBER_REGEX;
+
+    #[test]
+    fn test_checkpoint_stream_of_values() {
+        let source = "12 -3.5e2\n7";
+        let value = left(matching(&JSON_NUMBER_REGEX), maybe(whitespace));
+
+        let (_, (first, state)) = checkpoint(|i| value.parse(i), source)
+            .parse(source)
+            .unwrap();
+        assert_eq!("12", first);
+        assert_eq!(Some(3), state.offset());
+        assert_eq!("-3.5e2\n7", state.remainder());
+
+        let (next, second) = state.resume(&value).unwrap();
+        assert_eq!("-3.5e2", second);
+        assert_eq!(Some(10), next.offset());
+
+        // Resuming from the same checkpoint yields the same result
+        assert_eq!(Ok((next, "-3.5e2")), state.resume(&value));
+
+        let (last, third) = next.resume(&value).unwrap();
+        assert_eq!("7", third);
+        assert!(last.is_done());
+        assert!(last.resume(&value).is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_foreign_input() {
+        let input = String::from("abcdef");
+        let parser = checkpoint(literal::<_, String>("a"), "ab");
+        let (_, (_, state)) = parser.parse(&input[..]).unwrap();
+        assert_eq!(None, state.offset());
+        assert_eq!("bcdef", state.remainder());
+    }
+}
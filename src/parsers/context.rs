@@ -0,0 +1,128 @@
+use crate::parsers::*;
+use std::fmt;
+
+/// Error annotated with the stack of constructs the failure was nested in.
+///
+/// Frames are pushed from the innermost construct outward, as errors bubble up through
+/// `context` and `context_map`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextError<E> {
+    /// Error of the parser that failed.
+    pub error: E,
+
+    /// Labels of the enclosing constructs, innermost first.
+    pub frames: Vec<&'static str>,
+}
+
+/// Renders the frames outermost first, followed by the error.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let err = ContextError { error: "Oops", frames: vec!("number", "array") };
+/// assert_eq!("in array > in number: Oops", err.to_string());
+/// ```
+impl<E: fmt::Display> fmt::Display for ContextError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, frame) in self.frames.iter().rev().enumerate() {
+            if idx > 0 {
+                write!(f, " > ")?;
+            }
+            write!(f, "in {}", frame)?;
+        }
+
+        match self.frames.is_empty() {
+            true => write!(f, "{}", self.error),
+            false => write!(f, ": {}", self.error),
+        }
+    }
+}
+
+/// Labels the errors of a parser with a first context frame.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = context(identifier, "name");
+/// assert_eq!(Ok(("", "bob")), parser.parse("bob"));
+/// assert_eq!(vec!("name"), parser.parse("1").unwrap_err().frames);
+/// ```
+pub const fn context<P, I, R, O, E>(
+    parser: P,
+    frame: &'static str,
+) -> impl Parser<I, R, O, ContextError<E>>
+where
+    P: Parser<I, R, O, E>,
+{
+    move |input: I| {
+        parser.parse(input).map_err(|error| ContextError {
+            error,
+            frames: vec![frame],
+        })
+    }
+}
+
+/// Pushes a context frame onto the errors of an already contextualized parser.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = context_map(context(identifier, "name"), "declaration");
+/// assert_eq!(
+///     "in declaration > in name: Could not parse '\\A[a-zA-Z_][a-zA-Z0-9_]*'",
+///     parser.parse("1").unwrap_err().to_string()
+/// );
+/// ```
+pub const fn context_map<P, I, R, O, E>(
+    parser: P,
+    frame: &'static str,
+) -> impl Parser<I, R, O, ContextError<E>>
+where
+    P: Parser<I, R, O, ContextError<E>>,
+{
+    move |input: I| {
+        parser.parse(input).map_err(|mut err| {
+            err.frames.push(frame);
+            err
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regexes::JSON_NUMBER_REGEX;
+
+    #[test]
+    fn test_context_map_nested() {
+        let number = context(matching::<String>(&JSON_NUMBER_REGEX), "number");
+        let value = context_map(number, "value");
+        let object = context_map(right(context(literal("{\"a\":"), "key"), value), "object");
+        let array = context_map(right(context(literal("["), "bracket"), object), "array");
+
+        assert_eq!(Ok(("]", "1")), array.parse("[{\"a\":1]"));
+
+        let err = array.parse("[{\"a\":x]").unwrap_err();
+        assert_eq!(vec!("number", "value", "object", "array"), err.frames);
+        assert_eq!(
+            "in array > in object > in value > in number: Could not parse '\\A-?(?:0|[1-9]\\d*)(?:\\.\\d+)?(?:[eE][+-]?\\d+)?'",
+            err.to_string()
+        );
+
+        // Frames only cover the constructs the failure was nested in
+        let err = array.parse("{").unwrap_err();
+        assert_eq!(
+            "in array > in bracket: Could not parse '['",
+            err.to_string()
+        );
+    }
+}
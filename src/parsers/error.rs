@@ -0,0 +1,370 @@
+use crate::parsers::*;
+use std::fmt;
+
+/// Structured parsing error, locating the failure in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Description of the failure.
+    pub message: String,
+
+    /// Byte offset of the failure in the source.
+    pub offset: usize,
+
+    /// Labels of what was expected at the offset, empty if unknown.
+    pub expected: Vec<String>,
+}
+
+impl ParseError {
+    /// Builds an error for a parser that failed on the given input, which must be a slice of the
+    /// source. The offset falls back to 0 otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::parsers::*;
+    ///
+    /// let source = "[1, x]";
+    /// let err = ParseError::at("Unexpected 'x'", source, &source[4..]);
+    /// assert_eq!(4, err.offset);
+    /// ```
+    pub fn at<M: Into<String>>(message: M, source: &str, input: &str) -> Self {
+        Self {
+            message: message.into(),
+            offset: input.source_range_start(source).unwrap_or(0),
+            expected: Vec::new(),
+        }
+    }
+}
+
+/// Renders the message followed by the offset.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let err = ParseError { message: "Oops".to_string(), offset: 3, expected: vec!() };
+/// assert_eq!("Oops at byte 3", err.to_string());
+/// ```
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Wraps the errors of the existing parsers, which do not know where they failed.
+impl From<String> for ParseError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            offset: 0,
+            expected: Vec::new(),
+        }
+    }
+}
+
+/// Keeps only the message, for parsers that still fail with a `String`.
+impl From<ParseError> for String {
+    fn from(err: ParseError) -> Self {
+        err.message
+    }
+}
+
+/// Turns the errors of a parser into [`ParseError`]s located in the source.
+///
+/// The offset of the inner error, relative to the input the parser was given, is shifted by the
+/// offset of that input in the source. Leaf parsers such as `matching` or `literal` fail at their
+/// own input and `String` errors carry no offset, so both end up at the start of that input.
+///
+/// # Note
+/// The errors of a parser already wrapped in `located` are absolute, wrapping it again with the
+/// same source would shift them twice.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let source = "ab!";
+/// let parser = right(literal("ab"), located(literal::<_, ParseError>("c"), source));
+/// let err = parser.parse(source).unwrap_err();
+/// assert_eq!((2, "Could not parse 'c'"), (err.offset, err.message.as_str()));
+/// ```
+pub const fn located<'s, 'a, P, O, E>(
+    parser: P,
+    source: &'s str,
+) -> impl Parser<&'a str, &'a str, O, ParseError> + use<'s, 'a, P, O, E>
+where
+    P: Parser<&'a str, &'a str, O, E>,
+    E: Into<ParseError>,
+{
+    move |input: &'a str| {
+        parser.parse(input).map_err(|err| {
+            let err = err.into();
+            ParseError {
+                offset: input.source_range_start(source).unwrap_or(0) + err.offset,
+                ..err
+            }
+        })
+    }
+}
+
+/// Labels the errors of a parser with what it was expected to match.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = expecting(identifier, "a name");
+/// assert_eq!(vec!("a name".to_string()), parser.parse("1").unwrap_err().expected);
+/// ```
+pub const fn expecting<P, I, R, O, E>(
+    parser: P,
+    label: &'static str,
+) -> impl Parser<I, R, O, ParseError>
+where
+    P: Parser<I, R, O, E>,
+    E: Into<ParseError>,
+{
+    move |input: I| {
+        parser.parse(input).map_err(|err| ParseError {
+            expected: vec![label.to_string()],
+            ..err.into()
+        })
+    }
+}
+
+/// Same as `or`, but when both parsers fail, their expectations are combined.
+///
+/// # Result Conditions
+/// If both parser fails, fails with the error furthest in the source, listing what each parser
+/// expected when they failed at the same offset. The message of an error stands in for its
+/// expectations when it has none.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let dashes = expecting(literal::<_, ParseError>("--"), "'--'");
+/// let parser = or_expecting(expecting(identifier, "a name"), dashes);
+/// assert_eq!(
+///     "Expected one of: a name, '--'",
+///     parser.parse("?").unwrap_err().message
+/// );
+/// ```
+pub const fn or_expecting<PA, PB, I, R, O>(pa: PA, pb: PB) -> impl Parser<I, R, O, ParseError>
+where
+    PA: Parser<I, R, O, ParseError>,
+    PB: Parser<I, R, O, ParseError>,
+    I: Clone,
+{
+    let labels = |err: ParseError| match err.expected.is_empty() {
+        true => vec![err.message],
+        false => err.expected,
+    };
+
+    move |input: I| {
+        let err_a = match pa.parse(input.clone()) {
+            Ok(r) => return Ok(r),
+            Err(err) => err,
+        };
+        let err_b = match pb.parse(input) {
+            Ok(r) => return Ok(r),
+            Err(err) => err,
+        };
+
+        if err_a.offset != err_b.offset {
+            return Err(std::cmp::max_by_key(err_a, err_b, |err| err.offset));
+        }
+
+        let offset = err_a.offset;
+        let mut expected = labels(err_a);
+        expected.extend(labels(err_b));
+        Err(ParseError {
+            message: format!("Expected one of: {}", expected.join(", ")),
+            offset,
+            expected,
+        })
+    }
+}
+
+/// Renders an error as a diagnostic showing the line it occurred on, with a caret under the
+/// failing character.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let source = "[1,\n 2 x]";
+/// let err = ParseError::at("Expected ']'", source, &source[7..]);
+/// assert_eq!(
+///     "Expected ']'\n --> 2:4\n  |\n2 |  2 x]\n  |    ^",
+///     render_error(source, &err)
+/// );
+/// ```
+pub fn render_error(source: &str, err: &ParseError) -> String {
+    let LineCol { line, column } = line_col(source, err.offset);
+    let text = source.lines().nth(line - 1).unwrap_or("");
+
+    // Tabs are kept so that the caret lines up whatever their width
+    let padding: String = text
+        .chars()
+        .take(column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let gutter = " ".repeat(line.to_string().len());
+
+    format!(
+        "{}\n{}--> {}:{}\n{} |\n{} | {}\n{} | {}^",
+        err.message, gutter, line, column, gutter, line, text, gutter, padding
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regexes::*;
+
+    #[test]
+    fn test_located_matching() {
+        let source = "abc";
+        let err = located(matching::<ParseError>(&JSON_NUMBER_REGEX), source)
+            .parse(source)
+            .unwrap_err();
+        assert_eq!(0, err.offset);
+        assert_eq!(Vec::<String>::new(), err.expected);
+    }
+
+    #[test]
+    fn test_leaf_parsers_offset() {
+        let err: ParseError = matching(&JSON_NUMBER_REGEX).parse("abc").unwrap_err();
+        assert_eq!(0, err.offset);
+
+        let err: ParseError = literal("c").parse("ab!").unwrap_err();
+        assert_eq!((0, "Could not parse 'c'"), (err.offset, err.message.as_str()));
+    }
+
+    #[test]
+    fn test_located_leaf_in_sequence() {
+        let source = "[1, x]";
+        let prefix = || literal::<_, ParseError>("[1, ");
+        let err = right(prefix(), located(literal::<_, ParseError>("2"), source))
+            .parse(source)
+            .unwrap_err();
+        assert_eq!("Could not parse '2' at byte 4", err.to_string());
+
+        let number = located(matching::<ParseError>(&JSON_NUMBER_REGEX), source);
+        let err = right(prefix(), number).parse(source).unwrap_err();
+        assert_eq!(4, err.offset);
+    }
+
+    #[test]
+    fn test_located_shifts_inner_offset() {
+        let source = "ab=cd!";
+        let inner = |input: &'static str| match input.strip_prefix("cd") {
+            Some(rem) if rem.is_empty() => Ok((rem, "cd")),
+            _ => Err(ParseError::at("Expected the end", input, &input[2..])),
+        };
+        let err = right(literal::<_, ParseError>("ab="), located(inner, source))
+            .parse(source)
+            .unwrap_err();
+        assert_eq!("Expected the end at byte 5", err.to_string());
+    }
+
+    #[test]
+    fn test_located_nested() {
+        let source = "foo, bar, 1";
+        let items = while_(left(identifier, literal::<_, String>(", ")));
+        let err = right(located(items, source), located(identifier, source))
+            .parse(source)
+            .unwrap_err();
+        assert_eq!(10, err.offset);
+        assert_eq!(
+            "Could not parse '\\A[a-zA-Z_][a-zA-Z0-9_]*' at byte 10",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_string_conversions() {
+        let err = ParseError::from("Oops".to_string());
+        assert_eq!(0, err.offset);
+        assert_eq!("Oops".to_string(), String::from(err));
+
+        let copy = "abc".to_string();
+        assert_eq!(0, ParseError::at("Oops", "abc", &copy[1..]).offset);
+    }
+
+    #[test]
+    fn test_or_expecting_accumulates() {
+        let parser = or_expecting(
+            expecting(literal::<_, ParseError>("a"), "'a'"),
+            expecting(literal::<_, ParseError>("b"), "'b'"),
+        );
+        let parser = or_expecting(parser, expecting(identifier, "a name"));
+        let err = parser.parse("?").unwrap_err();
+        assert_eq!(vec!("'a'", "'b'", "a name"), err.expected);
+        assert_eq!("Expected one of: 'a', 'b', a name", err.message);
+        assert_eq!(Ok(("", "b")), parser.parse("b"));
+    }
+
+    #[test]
+    fn test_or_expecting_furthest() {
+        let source = "ab?";
+        let at = |expected| located(literal::<_, ParseError>(expected), source);
+        let pa = right(at("a"), at("c"));
+        let pb = right(at("ab"), at("c"));
+        let unlabeled = or_expecting(at("x"), at("y"));
+
+        assert_eq!(2, or_expecting(pa, pb).parse(source).unwrap_err().offset);
+        assert_eq!(
+            vec!("Could not parse 'x'", "Could not parse 'y'"),
+            unlabeled.parse(source).unwrap_err().expected
+        );
+    }
+
+    #[test]
+    fn test_render_error_sample() {
+        let source = include_str!("../json/sample.json")
+            .replace("\"servlet-name\": \"cofaxEmail\"", "\"servlet-name\": cofaxEmail");
+        let offset = source.find("cofaxEmail,").unwrap();
+        let err = ParseError::at("Could not parse value", &source, &source[offset..]);
+
+        let rendered = render_error(&source, &err);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(
+            vec!(
+                "Could not parse value",
+                "  --> 50:23",
+                "   |",
+                "50 |       \"servlet-name\": cofaxEmail,",
+                "   |                       ^",
+            ),
+            lines
+        );
+    }
+
+    #[test]
+    fn test_render_error_edges() {
+        let err = |offset| ParseError {
+            message: "Oops".to_string(),
+            offset,
+            expected: Vec::new(),
+        };
+
+        assert_eq!("Oops\n --> 1:1\n  |\n1 | \n  | ^", render_error("", &err(0)));
+        assert_eq!(
+            "Oops\n --> 2:3\n  |\n2 | \tx\n  | \t ^",
+            render_error("a\n\tx", &err(4))
+        );
+        assert_eq!(
+            "Oops\n --> 1:3\n  |\n1 | ab\n  |   ^",
+            render_error("ab\r\ncd", &err(2))
+        );
+    }
+}
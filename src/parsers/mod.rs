@@ -1,9 +1,44 @@
+mod backtrack;
+pub use backtrack::*;
+
 mod base_parsers;
 pub use base_parsers::*;
 
+mod byte_parsers;
+pub use byte_parsers::*;
+
+mod checkpoint;
+pub use checkpoint::*;
+
+mod context;
+pub use context::*;
+
+mod error;
+pub use error::*;
+
+mod ops;
+pub use ops::*;
+
+mod permutation;
+pub use permutation::*;
+
+mod recovery;
+pub use recovery::*;
+
+mod recursive;
+pub use recursive::*;
+
 mod source_range;
 pub use source_range::*;
 
+mod str_combinators;
+pub use str_combinators::*;
+
+#[cfg(feature = "debug")]
+mod trace;
+#[cfg(feature = "debug")]
+pub use trace::*;
+
 
 /// Main parser trait, pivotal to the library.
 ///
@@ -29,6 +64,120 @@ where
     }
 }
 
+/// Implementation of the Parser trait for boxed parsers, so that parsers of different types can
+/// be stored together.
+impl<I, R, O, E> Parser<I, R, O, E> for Box<dyn Parser<I, R, O, E> + '_> {
+    fn parse(&self, input: I) -> Result<(R, O), E> {
+        self.as_ref().parse(input)
+    }
+}
+
+/// Type erased parser, see [`boxed`].
+pub type BoxedParser<'p, I, R, O, E> = Box<dyn Parser<I, R, O, E> + 'p>;
+
+/// Erases the type of a parser by boxing it.
+///
+/// Keeps the types of deeply nested combinators small, and allows functions building recursive
+/// grammars to name their return type, which `impl Parser` cannot do for recursive types.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// // Depth of nested brackets around an `x`
+/// fn depth<'a>() -> BoxedParser<'a, &'a str, &'a str, usize, String> {
+///     let nested = middle(literal("["), |input| depth().parse(input), literal("]"));
+///     boxed(or(map(literal("x"), |_| 0), map(nested, |d| d + 1)))
+/// }
+///
+/// assert_eq!(Ok(("", 3)), depth().parse("[[[x]]]"));
+/// assert!(depth().parse("[[x]").is_err());
+/// ```
+pub fn boxed<'p, P, I, R, O, E>(parser: P) -> BoxedParser<'p, I, R, O, E>
+where
+    P: Parser<I, R, O, E> + 'p,
+{
+    Box::new(parser)
+}
+
+/// Method chaining counterparts of the combinator functions, so that grammars can be written
+/// fluently rather than inside out.
+///
+/// Each method forwards to the function of the same name, `many` forwards to `while_`.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = identifier.left(whitespace).many();
+/// assert_eq!(Ok(("", vec!("ident1", "ident2"))), parser.parse("ident1 ident2 "));
+///
+/// let parser = literal("-").right(identifier).or(identifier).map(str::len);
+/// assert_eq!(Ok(("", 4)), parser.parse("-flag"));
+/// ```
+pub trait ParserExt<I, R, O, E>: Parser<I, R, O, E> + Sized {
+    /// See [`map`].
+    fn map<OB, FN>(self, map_fn: FN) -> impl Parser<I, R, OB, E>
+    where
+        FN: Fn(O) -> OB,
+    {
+        map(self, map_fn)
+    }
+
+    /// See [`and`].
+    fn and<PB, RB, OB>(self, pb: PB) -> impl Parser<I, RB, (O, OB), E>
+    where
+        PB: Parser<R, RB, OB, E>,
+    {
+        and(self, pb)
+    }
+
+    /// See [`or`].
+    fn or<PB>(self, pb: PB) -> impl Parser<I, R, O, E>
+    where
+        PB: Parser<I, R, O, E>,
+        I: Clone,
+    {
+        or(self, pb)
+    }
+
+    /// See [`left`].
+    fn left<PB, RB, OB>(self, pb: PB) -> impl Parser<I, RB, O, E>
+    where
+        PB: Parser<R, RB, OB, E>,
+    {
+        left(self, pb)
+    }
+
+    /// See [`right`].
+    fn right<PB, RB, OB>(self, pb: PB) -> impl Parser<I, RB, OB, E>
+    where
+        PB: Parser<R, RB, OB, E>,
+    {
+        right(self, pb)
+    }
+
+    /// See [`while_`].
+    fn many(self) -> impl Parser<I, I, Vec<O>, E>
+    where
+        Self: Parser<I, I, O, E>,
+        I: Clone,
+    {
+        while_(self)
+    }
+
+    /// See [`boxed`].
+    fn boxed<'p>(self) -> BoxedParser<'p, I, R, O, E>
+    where
+        Self: 'p,
+    {
+        boxed(self)
+    }
+}
+
+/// Auto implementation of the ParserExt trait for all parsers.
+impl<P, I, R, O, E> ParserExt<I, R, O, E> for P where P: Parser<I, R, O, E> {}
+
 /// Parser that always succeed given any input.
 ///
 /// # Result Conditions
@@ -71,6 +220,91 @@ where
     }
 }
 
+/// Allow the fallible transformation of a parser's output into another output.
+///
+/// # Result Conditions
+/// Fails if the parser fails, or with the error of the transformation if it fails.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let digits = take_while1(|c| c.is_ascii_digit());
+/// let parser = map_res(digits, |n: &str| n.parse::<u8>().map_err(|err| err.to_string()));
+/// assert_eq!(Ok(("", 255)), parser.parse("255"));
+/// assert_eq!(
+///     Err("number too large to fit in target type".to_string()),
+///     parser.parse("256")
+/// );
+/// ```
+pub const fn map_res<P, I, R, OA, OB, E, FN>(pa: P, f: FN) -> impl Parser<I, R, OB, E>
+where
+    P: Parser<I, R, OA, E>,
+    FN: Fn(OA) -> Result<OB, E>,
+{
+    move |input: I| {
+        let (remainder, ret) = pa.parse(input)?;
+        Ok((remainder, f(ret)?))
+    }
+}
+
+/// Allow the transformation of a parser's error into another error.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct NotAnIdentifier;
+///
+/// let parser = map_err(identifier, |_| NotAnIdentifier);
+/// assert_eq!(Ok(("", "foo")), parser.parse("foo"));
+/// assert_eq!(Err(NotAnIdentifier), parser.parse("1"));
+/// ```
+pub const fn map_err<P, I, R, O, EA, EB, FN>(parser: P, map_fn: FN) -> impl Parser<I, R, O, EB>
+where
+    P: Parser<I, R, O, EA>,
+    FN: Fn(EA) -> EB,
+{
+    move |input: I| parser.parse(input).map_err(&map_fn)
+}
+
+/// Allow the transformation of a parser's error, given the input the parser failed on.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+/// use parser_combinator::regexes::*;
+///
+/// let parser = map_err_with_input(matching(&JSON_NUMBER_REGEX), |input: &str, _| {
+///     let snippet: String = input.chars().take(3).collect();
+///     format!("failed to parse number at: '{}...'", snippet)
+/// });
+/// assert_eq!(Ok(("", "42")), parser.parse("42"));
+/// assert_eq!(
+///     Err("failed to parse number at: 'xyz...'".to_string()),
+///     parser.parse("xyz123")
+/// );
+/// ```
+pub const fn map_err_with_input<P, I, R, O, E, FN>(parser: P, map_fn: FN) -> impl Parser<I, R, O, E>
+where
+    P: Parser<I, R, O, E>,
+    FN: Fn(I, E) -> E,
+    I: Clone,
+{
+    move |input: I| {
+        parser
+            .parse(input.clone())
+            .map_err(|err| map_fn(input, err))
+    }
+}
+
 /// Takes two parsers and return the result of both in a tuple.
 ///
 /// # Result Conditions
@@ -95,12 +329,47 @@ where
     }
 }
 
+/// Applies a parser, then the parser built from its output on the remainder.
+///
+/// This is the monadic bind of parsers: where `and` sequences two fixed parsers, the second
+/// parser here depends on what the first one produced, which allows context sensitive grammars
+/// such as length prefixed data.
+///
+/// # Result Conditions
+/// If either parser fails, the combined parser also fails. Returns the output of the second one.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let len = map(take_while1(|c| c.is_ascii_digit()), |n: &str| n.parse().unwrap());
+/// let parser = and_then(len, |n| count(right(literal(":"), identifier), n));
+/// assert_eq!(Ok((":c", vec!("a", "b"))), parser.parse("2:a:b:c"));
+/// assert!(parser.parse("3:a:b").is_err());
+/// ```
+pub const fn and_then<PA, I, RA, OA, E, FN, PB, RB, OB>(
+    pa: PA,
+    f: FN,
+) -> impl Parser<I, RB, OB, E>
+where
+    PA: Parser<I, RA, OA, E>,
+    FN: Fn(OA) -> PB,
+    PB: Parser<RA, RB, OB, E>,
+{
+    move |input: I| {
+        let (remainder, ret) = pa.parse(input)?;
+        f(ret).parse(remainder)
+    }
+}
+
 /// Takes two parsers and returns which ever result matches first.
 ///
 /// Tries the first parser and then the second.
 ///
 /// # Result Conditions
-/// If both parser fails, the combined parser also fails. Otherwise succeeds.
+/// If both parser fails, the combined parser also fails with the error of the second parser.
+/// Otherwise succeeds. Use `or_merge` to keep both errors, or `or_expecting` to keep the one
+/// furthest in the source.
 ///
 /// # Example
 /// ```
@@ -123,10 +392,152 @@ where
 {
     move |input: I| match pa.parse(input.clone()) {
         Ok(r) => Ok(r),
-        Err(err) => match pb.parse(input) {
-            Ok(r) => Ok(r),
-            Err(err) => Err(err),
-        },
+        Err(_) => pb.parse(input),
+    }
+}
+
+/// Same as `or`, but when both parsers fail, their errors are combined.
+///
+/// # Result Conditions
+/// If both parser fails, fails with the result of `merge` given the error of the first parser
+/// then the one of the second. Otherwise succeeds.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = or_merge(literal("true"), literal("false"), |a, b| format!("{} or {}", a, b));
+/// assert_eq!(Ok(("", "false")), parser.parse("false"));
+/// assert_eq!(
+///     Err("Could not parse 'true' or Could not parse 'false'".to_string()),
+///     parser.parse("maybe")
+/// );
+/// ```
+pub const fn or_merge<PA, PB, I, R, O, E, FN>(pa: PA, pb: PB, merge: FN) -> impl Parser<I, R, O, E>
+where
+    PA: Parser<I, R, O, E>,
+    PB: Parser<I, R, O, E>,
+    FN: Fn(E, E) -> E,
+    I: Clone,
+{
+    move |input: I| match pa.parse(input.clone()) {
+        Ok(r) => Ok(r),
+        Err(err_a) => pb.parse(input).map_err(|err_b| merge(err_a, err_b)),
+    }
+}
+
+/// Takes a slice of parsers and returns the result of the first one to match.
+///
+/// Tries the parsers in order, like chained `or`s. Boxing parsers allows mixing parsers of
+/// different types.
+///
+/// # Result Conditions
+/// Returns the first success, or the error of the last parser if they all fail.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+/// use parser_combinator::regexes::*;
+///
+/// // Same grammar as the terminal values of the json parser, without the nested `or`s
+/// let terminals: Vec<Box<dyn Parser<&str, &str, &str, String>>> = vec![
+///     Box::new(quoted_string('"', '\\')),
+///     Box::new(matching(&JSON_NUMBER_REGEX)),
+///     Box::new(literal("true")),
+///     Box::new(literal("false")),
+///     Box::new(literal("null")),
+/// ];
+///
+/// let parser = left(choice(&terminals), maybe(whitespace));
+/// assert_eq!(Ok(("", "foo")), parser.parse("\"foo\" "));
+/// assert_eq!(Ok((",", "-1.5")), parser.parse("-1.5,"));
+/// assert_eq!(Ok(("", "null")), parser.parse("null"));
+/// assert_eq!(Err("Could not parse 'null'".to_string()), parser.parse("nil"));
+/// ```
+///
+/// # Note
+/// Panics if `parsers` is empty, as there would be no error to return.
+pub const fn choice<'p, P, I, R, O, E>(parsers: &'p [P]) -> impl Parser<I, R, O, E> + 'p
+where
+    P: Parser<I, R, O, E>,
+    I: Clone,
+{
+    assert!(!parsers.is_empty(), "choice needs at least one parser");
+
+    move |input: I| {
+        let (last, rest) = parsers.split_last().expect("Checked on creation");
+        for parser in rest {
+            if let Ok(r) = parser.parse(input.clone()) {
+                return Ok(r);
+            }
+        }
+        last.parse(input)
+    }
+}
+
+/// Applies a parser without consuming any input.
+///
+/// # Result Conditions
+/// Same as input parser, but the remainder is always the given input.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = peek(identifier);
+/// assert_eq!(Ok(("ident rest", "ident")), parser.parse("ident rest"));
+/// assert!(parser.parse("1").is_err());
+/// ```
+pub const fn peek<P, I, R, O, E>(parser: P) -> impl Parser<I, I, O, E>
+where
+    P: Parser<I, R, O, E>,
+    I: Clone,
+{
+    move |input: I| {
+        let (_, out) = parser.parse(input.clone())?;
+        Ok((input, out))
+    }
+}
+
+/// Peeks with a condition parser to choose which of two parsers to apply.
+///
+/// The condition never consumes input, both branches receive the input it was given.
+///
+/// # Result Conditions
+/// Same as `then` if the condition succeeds, same as `els` otherwise.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+/// use parser_combinator::regexes::*;
+///
+/// let parser = if_peek(
+///     literal("\""),
+///     quoted_string('"', '\\'),
+///     matching(&JSON_NUMBER_REGEX),
+/// );
+/// assert_eq!(Ok(("", "foo")), parser.parse("\"foo\""));
+/// assert_eq!(Ok(("", "-12")), parser.parse("-12"));
+/// assert!(parser.parse("\"unterminated").is_err());
+/// ```
+///
+/// # Note
+/// Unlike `or`, `then` is never attempted speculatively, which is worth it when the branches
+/// are expensive and the lookahead is cheap.
+pub const fn if_peek<C, T, F, I, R, O, CR, CO, E>(
+    cond: C,
+    then: T,
+    els: F,
+) -> impl Parser<I, R, O, E>
+where
+    C: Parser<I, CR, CO, E>,
+    T: Parser<I, R, O, E>,
+    F: Parser<I, R, O, E>,
+    I: Clone,
+{
+    move |input: I| match cond.parse(input.clone()) {
+        Ok(_) => then.parse(input),
+        Err(_) => els.parse(input),
     }
 }
 
@@ -150,130 +561,794 @@ where
     map(and(pa, pb), |(_, b)| b)
 }
 
-/// Takes 2 parsers as argument and return the result of the left parser. Both parser must succeed.
+/// Takes 2 parsers as argument and return the result of the left parser. Both parser must succeed.
+///
+/// # Result Conditions
+/// Both parser must succeed.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = left(identifier, whitespace);
+/// assert_eq!(Ok(("", "ident")), parser.parse("ident "));
+/// ```
+pub const fn left<PA, IA, RA, OA, PB, RB, OB, E>(pa: PA, pb: PB) -> impl Parser<IA, RB, OA, E>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    map(and(pa, pb), |(a, _)| a)
+}
+
+/// Combination of the right, middle and left parser.
+///
+/// Combination of the right and left parser such that given parser A B C it returns the result
+/// of B and the output of C or an exception of type E given an input valid for A.
+///
+/// # Result Conditions
+/// All three parsers must succeed.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = middle(whitespace, identifier, whitespace);
+/// assert_eq!(Ok(("", "ident")), parser.parse("\n ident \r"));
+/// ```
+pub const fn middle<PA, IA, RA, OA, PB, RB, OB, PC, RC, OC, E>(
+    pa: PA,
+    pb: PB,
+    pc: PC,
+) -> impl Parser<IA, RC, OB, E>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+    PC: Parser<RB, RC, OC, E>,
+{
+    let p = map(and(pa, pb), |(_, b)| b);
+    map(and(p, pc), |(m, _)| m)
+}
+
+/// Same as `right`, under the name most parser combinator libraries use.
+///
+/// # Result Conditions
+/// Both parser must succeed.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = preceded(identifier, whitespace);
+/// assert_eq!(Ok(("", " ")), parser.parse("ident "));
+/// ```
+pub const fn preceded<PA, IA, RA, OA, PB, RB, OB, E>(pa: PA, pb: PB) -> impl Parser<IA, RB, OB, E>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    right(pa, pb)
+}
+
+/// Same as `left`, under the name most parser combinator libraries use.
+///
+/// # Result Conditions
+/// Both parser must succeed.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = terminated(identifier, whitespace);
+/// assert_eq!(Ok(("", "ident")), parser.parse("ident "));
+/// ```
+pub const fn terminated<PA, IA, RA, OA, PB, RB, OB, E>(pa: PA, pb: PB) -> impl Parser<IA, RB, OA, E>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    left(pa, pb)
+}
+
+/// Same as `middle`, under the name most parser combinator libraries use.
+///
+/// # Result Conditions
+/// All three parsers must succeed.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = delimited(whitespace, identifier, whitespace);
+/// assert_eq!(Ok(("", "ident")), parser.parse("\n ident \r"));
+/// ```
+pub const fn delimited<PA, IA, RA, OA, PB, RB, OB, PC, RC, OC, E>(
+    pa: PA,
+    pb: PB,
+    pc: PC,
+) -> impl Parser<IA, RC, OB, E>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+    PC: Parser<RB, RC, OC, E>,
+{
+    middle(pa, pb, pc)
+}
+
+/// Applies a runtime list of parsers one after the other and collects their outputs.
+///
+/// Runtime counterpart of chaining `and` for parsers sharing an output type.
+///
+/// # Result Conditions
+/// Every parser must succeed. Succeeds with an empty vector given an empty list.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let mut parsers: Vec<Box<dyn Parser<&str, &str, &str, String>>> = Vec::new();
+/// parsers.push(Box::new(identifier));
+/// parsers.push(Box::new(whitespace));
+///
+/// let parser = sequence_of(parsers);
+/// assert_eq!(Ok(("", vec!("ident", " "))), parser.parse("ident "));
+/// assert!(parser.parse("ident").is_err());
+/// ```
+pub const fn sequence_of<'p, I, O, E>(
+    parsers: Vec<Box<dyn Parser<I, I, O, E> + 'p>>,
+) -> impl Parser<I, I, Vec<O>, E> + 'p
+where
+    I: 'p,
+    O: 'p,
+    E: 'p,
+{
+    move |input: I| {
+        let mut rem = input;
+        let mut res = Vec::with_capacity(parsers.len());
+
+        for parser in &parsers {
+            let (new_rem, out) = parser.parse(rem)?;
+            rem = new_rem;
+            res.push(out);
+        }
+        Ok((rem, res))
+    }
+}
+
+/// Applies a parser 0 or more time. Always succeeds.
+///
+/// # Result Conditions
+/// Always succeeds. Returns as soon as the given parser fails.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = while_(left(identifier, whitespace));
+/// assert_eq!(Ok(("", vec!("ident1", "ident2"))), parser.parse("ident1 ident2 "));
+/// assert_eq!(Ok(("", vec!())), parser.parse(""));
+/// ```
+pub const fn while_<P, I, O, E>(parser: P) -> impl Parser<I, I, Vec<O>, E>
+where
+    P: Parser<I, I, O, E>,
+    I: Clone,
+{
+    while_capacity(parser, 0)
+}
+
+/// Same as `while_`, but reserves room for `capacity` outputs upfront.
+///
+/// Avoids growing the output one reallocation at a time when the number of repetitions can be
+/// estimated, such as the size of a large array.
+///
+/// # Result Conditions
+/// Always succeeds. Returns as soon as the given parser fails.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = while_capacity(left(identifier, whitespace), 16);
+/// let (rem, idents) = parser.parse("ident1 ident2 ").unwrap();
+/// assert_eq!(("", &vec!("ident1", "ident2")), (rem, &idents));
+/// assert!(idents.capacity() >= 16);
+/// ```
+///
+/// # Note
+/// The input is cloned before each attempt, so that it can be returned once the parser fails.
+/// For `Copy` inputs such as slices, the clone is a plain copy.
+pub const fn while_capacity<P, I, O, E>(parser: P, capacity: usize) -> impl Parser<I, I, Vec<O>, E>
+where
+    P: Parser<I, I, O, E>,
+    I: Clone,
+{
+    move |input: I| {
+        let mut rem = input;
+        let mut res = Vec::with_capacity(capacity);
+
+        while let Ok((new_rem, out)) = parser.parse(rem.clone()) {
+            rem = new_rem;
+            res.push(out)
+        }
+        Ok((rem, res))
+    }
+}
+
+/// Applies a parser 1 or more time. Stops when the parser fails.
+///
+/// # Result Conditions
+/// Succeed if the first parse succeeds and returns as soon as the given parser fails afterward.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = one_or_more(left(identifier, whitespace));
+/// assert_eq!(Ok(("", vec!("ident1", "ident2"))), parser.parse("ident1 ident2 "));
+/// assert!(parser.parse("").is_err());
+/// ```
+pub const fn one_or_more<P, I, O, E>(parser: P) -> impl Parser<I, I, Vec<O>, E>
+where
+    P: Parser<I, I, O, E>,
+    I: Clone,
+{
+    move |input: I| {
+        // The first attempt takes the input, its failure being returned as is
+        let (mut rem, first_out) = parser.parse(input)?;
+        let mut res = vec![first_out];
+
+        while let Ok((new_rem, out)) = parser.parse(rem.clone()) {
+            rem = new_rem;
+            res.push(out)
+        }
+        Ok((rem, res))
+    }
+}
+
+/// Applies a parser 0 or more time, folding each output into an accumulator. Always succeeds.
+///
+/// Same as `while_` without collecting the outputs into a `Vec`.
+///
+/// # Result Conditions
+/// Always succeeds. Returns as soon as the given parser fails.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let digit = map(take_while1(|c| c.is_ascii_digit()), |d: &str| d.parse::<u32>().unwrap());
+/// let parser = fold_many0(left(digit, maybe(whitespace)), 0, |sum, d| sum + d);
+/// assert_eq!(Ok(("", 11)), parser.parse("1 2 8"));
+/// assert_eq!(Ok(("x", 0)), parser.parse("x"));
+/// ```
+///
+/// # Note
+/// The initial accumulator is cloned on each parse, as the parser can be used several times.
+pub const fn fold_many0<P, I, O, E, A, FN>(parser: P, init: A, f: FN) -> impl Parser<I, I, A, E>
+where
+    P: Parser<I, I, O, E>,
+    FN: Fn(A, O) -> A,
+    I: Clone,
+    A: Clone,
+{
+    move |input: I| {
+        let mut rem = input;
+        let mut acc = init.clone();
+
+        while let Ok((new_rem, out)) = parser.parse(rem.clone()) {
+            rem = new_rem;
+            acc = f(acc, out);
+        }
+        Ok((rem, acc))
+    }
+}
+
+/// Applies a parser 1 or more time, folding each output into an accumulator.
+///
+/// Same as `one_or_more` without collecting the outputs into a `Vec`.
+///
+/// # Result Conditions
+/// Succeed if the first parse succeeds and returns as soon as the given parser fails afterward.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = fold_many1(literal_ci("a"), String::new(), |mut s, a| {
+///     s.push_str(a);
+///     s
+/// });
+/// assert_eq!(Ok(("b", "aAa".to_string())), parser.parse("aAab"));
+/// assert!(parser.parse("b").is_err());
+/// ```
+///
+/// # Note
+/// The initial accumulator is cloned on each parse, as the parser can be used several times.
+pub const fn fold_many1<P, I, O, E, A, FN>(parser: P, init: A, f: FN) -> impl Parser<I, I, A, E>
+where
+    P: Parser<I, I, O, E>,
+    FN: Fn(A, O) -> A,
+    I: Clone,
+    A: Clone,
+{
+    move |input: I| {
+        let (mut rem, first_out) = parser.parse(input)?;
+        let mut acc = f(init.clone(), first_out);
+
+        while let Ok((new_rem, out)) = parser.parse(rem.clone()) {
+            rem = new_rem;
+            acc = f(acc, out);
+        }
+        Ok((rem, acc))
+    }
+}
+
+/// Applies a parser 0 or more time, discarding the outputs. Always succeeds.
+///
+/// Same as `while_` without allocating, for parsers only used to consume input.
+///
+/// # Result Conditions
+/// Always succeeds. Returns as soon as the given parser fails.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = skip_many(or(whitespace, comment));
+/// assert_eq!(Ok(("x", ())), parser.parse(" // a\n /* b */ x"));
+/// assert_eq!(Ok(("x", ())), parser.parse("x"));
+/// ```
+pub const fn skip_many<P, I, O, E>(parser: P) -> impl Parser<I, I, (), E>
+where
+    P: Parser<I, I, O, E>,
+    I: Clone,
+{
+    fold_many0(parser, (), |_, _| ())
+}
+
+/// Applies a parser 1 or more time, discarding the outputs.
+///
+/// Same as `one_or_more` without allocating, for parsers only used to consume input.
+///
+/// # Result Conditions
+/// Succeed if the first parse succeeds and returns as soon as the given parser fails afterward.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = skip_many1(literal::<_, String>("-"));
+/// assert_eq!(Ok((">", ())), parser.parse("--->"));
+/// assert!(parser.parse(">").is_err());
+/// ```
+pub const fn skip_many1<P, I, O, E>(parser: P) -> impl Parser<I, I, (), E>
+where
+    P: Parser<I, I, O, E>,
+    I: Clone,
+{
+    fold_many1(parser, (), |_, _| ())
+}
+
+/// Applies a character parser 0 or more time, gathering the characters into a `String`. Always
+/// succeeds.
+///
+/// Shorthand for `collect_many` when the collection is a `String`.
+///
+/// # Result Conditions
+/// Always succeeds. Returns as soon as the given parser fails.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = many_chars(satisfy(|c| c.is_ascii_digit()));
+/// assert_eq!(Ok(("abc", "12345".to_string())), parser.parse("12345abc"));
+/// assert_eq!(Ok(("abc", String::new())), parser.parse("abc"));
+/// ```
+pub const fn many_chars<P, I, E>(parser: P) -> impl Parser<I, I, String, E>
+where
+    P: Parser<I, I, char, E>,
+    I: Clone,
+{
+    collect_many(parser)
+}
+
+/// Applies a parser 0 or more time, collecting the outputs into any collection. Always succeeds.
+///
+/// Same as `while_` for any `FromIterator`, the collection being picked by type inference.
+///
+/// # Result Conditions
+/// Always succeeds. Returns as soon as the given parser fails.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+/// use std::collections::BTreeSet;
+///
+/// let parser = collect_many(left(identifier, maybe(whitespace)));
+/// let expected: BTreeSet<&str> = ["a", "b"].into();
+/// assert_eq!(Ok(("", expected)), parser.parse("b a b"));
+/// ```
+pub const fn collect_many<P, I, O, E, C>(parser: P) -> impl Parser<I, I, C, E>
+where
+    P: Parser<I, I, O, E>,
+    I: Clone,
+    C: FromIterator<O>,
+{
+    move |input: I| {
+        let mut rem = input;
+
+        let outputs = std::iter::from_fn(|| {
+            let (new_rem, out) = parser.parse(rem.clone()).ok()?;
+            rem = new_rem;
+            Some(out)
+        });
+        let res = outputs.collect();
+        Ok((rem, res))
+    }
+}
+
+/// Parses operands separated by left-associative binary operators, such as `1 - 2 - 3` parsed
+/// as `(1 - 2) - 3`.
+///
+/// The operator parser yields the function combining the operands on each side of it.
+///
+/// # Result Conditions
+/// Succeed if the first operand parses. Returns as soon as either parser fails, an operator not
+/// followed by an operand is not consumed.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let number = map(take_while1(|c| c.is_ascii_digit()), |n: &str| n.parse::<i64>().unwrap());
+/// let minus = map(literal("-"), |_| |a, b| a - b);
+/// let parser = chainl1(number, minus);
+/// assert_eq!(Ok(("", -4)), parser.parse("1-2-3"));
+/// assert_eq!(Ok(("-", 7)), parser.parse("7-"));
+/// ```
+pub const fn chainl1<P, OP, I, O, E, FN>(operand: P, op: OP) -> impl Parser<I, I, O, E>
+where
+    P: Parser<I, I, O, E>,
+    OP: Parser<I, I, FN, E>,
+    FN: Fn(O, O) -> O,
+    I: Clone,
+{
+    move |input: I| {
+        let (mut rem, mut acc) = operand.parse(input)?;
+
+        while let Ok((op_rem, combine)) = op.parse(rem.clone()) {
+            match operand.parse(op_rem) {
+                Ok((new_rem, out)) => {
+                    rem = new_rem;
+                    acc = combine(acc, out);
+                }
+                Err(_) => break,
+            }
+        }
+        Ok((rem, acc))
+    }
+}
+
+/// Applies an item parser 0 or more time, separated by a separator parser. Always succeeds.
+///
+/// The outputs of the separator are discarded.
+///
+/// # Result Conditions
+/// Always succeeds. Returns as soon as either parser fails, a separator not followed by an item
+/// is not consumed.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = sep_by(identifier, literal(","));
+/// assert_eq!(Ok(("", vec!("a", "b", "c"))), parser.parse("a,b,c"));
+/// assert_eq!(Ok((",", vec!("a"))), parser.parse("a,"));
+/// assert_eq!(Ok(("1", vec!())), parser.parse("1"));
+/// ```
+pub const fn sep_by<P, S, I, O, SO, E>(item: P, separator: S) -> impl Parser<I, I, Vec<O>, E>
+where
+    P: Parser<I, I, O, E>,
+    S: Parser<I, I, SO, E>,
+    I: Clone,
+{
+    let parser = sep_by1(item, separator);
+
+    move |input: I| match parser.parse(input.clone()) {
+        Ok(r) => Ok(r),
+        Err(_) => Ok((input, Vec::new())),
+    }
+}
+
+/// Applies an item parser 1 or more time, separated by a separator parser.
+///
+/// The outputs of the separator are discarded.
+///
+/// # Result Conditions
+/// Fails with the error of the first item if it fails. Otherwise same as `sep_by`.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = sep_by1(identifier, literal(","));
+/// assert_eq!(Ok(("", vec!("a", "b"))), parser.parse("a,b"));
+/// assert!(parser.parse("").is_err());
+/// ```
+pub const fn sep_by1<P, S, I, O, SO, E>(item: P, separator: S) -> impl Parser<I, I, Vec<O>, E>
+where
+    P: Parser<I, I, O, E>,
+    S: Parser<I, I, SO, E>,
+    I: Clone,
+{
+    sep_fold1(
+        item,
+        separator,
+        |out| vec![out],
+        |mut res, out| {
+            res.push(out);
+            res
+        },
+    )
+}
+
+/// Applies a parser exactly `n` times.
+///
+/// # Result Conditions
+/// Fails if any of the `n` applications fails. Stops at `n` even if more would succeed, and
+/// always succeeds without consuming input when `n` is 0.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = count(left(identifier, whitespace), 2);
+/// assert_eq!(Ok(("c ", vec!("a", "b"))), parser.parse("a b c "));
+/// assert!(parser.parse("a ").is_err());
+/// ```
+pub const fn count<P, I, O, E>(parser: P, n: usize) -> impl Parser<I, I, Vec<O>, E>
+where
+    P: Parser<I, I, O, E>,
+{
+    move |input: I| {
+        let mut rem = input;
+        let mut res = Vec::with_capacity(n);
+
+        for _ in 0..n {
+            let (new_rem, out) = parser.parse(rem)?;
+            rem = new_rem;
+            res.push(out);
+        }
+        Ok((rem, res))
+    }
+}
+
+/// Applies a parser between `min` and `max` times.
+///
+/// # Result Conditions
+/// Fails if fewer than `min` applications succeed. Stops consuming once `max` is reached, and
+/// always succeeds without consuming input when `min` and `max` are both 0.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = many_m_n(2, 4, or(literal::<_, String>("1"), literal("2")));
+/// assert_eq!(Ok(("", vec!("1", "2"))), parser.parse("12"));
+/// assert_eq!(Ok(("1", vec!("1", "2", "2", "1"))), parser.parse("12211"));
+/// assert!(parser.parse("1").is_err());
+/// ```
+///
+/// # Note
+/// If `min` is greater than `max`, `max` is raised to `min`.
+pub const fn many_m_n<P, I, O, E>(
+    min: usize,
+    max: usize,
+    parser: P,
+) -> impl Parser<I, I, Vec<O>, E>
+where
+    P: Parser<I, I, O, E>,
+    I: Clone,
+{
+    move |input: I| {
+        let mut rem = input;
+        let mut res = Vec::new();
+
+        while res.len() < max.max(min) {
+            match parser.parse(rem.clone()) {
+                Ok((new_rem, out)) => {
+                    rem = new_rem;
+                    res.push(out);
+                }
+                Err(err) if res.len() < min => return Err(err),
+                Err(_) => break,
+            }
+        }
+        Ok((rem, res))
+    }
+}
+
+/// Applies an item parser until a terminator parser succeeds.
+///
+/// The terminator is tried first, before each item, and is consumed.
 ///
 /// # Result Conditions
-/// Both parser must succeed.
+/// Succeeds with the items and the output of the terminator once it matches. Fails if an item
+/// fails before the terminator is seen.
 ///
 /// # Example
 /// ```
 /// use parser_combinator::parsers::*;
+/// use regex::Regex;
 ///
-/// let parser = left(identifier, whitespace);
-/// assert_eq!(Ok(("", "ident")), parser.parse("ident "));
+/// let any_char = Regex::new(r"(?s)\A.").unwrap();
+/// let open = literal::<_, String>("/*");
+/// let parser = right(open, many_till(matching(&any_char), literal("*/")));
+/// assert_eq!(
+///     Ok((" tail", (vec!(" ", "a", "*", " "), "*/"))),
+///     parser.parse("/* a* */ tail")
+/// );
+/// assert!(parser.parse("/* unterminated").is_err());
 /// ```
-pub const fn left<PA, IA, RA, OA, PB, RB, OB, E>(pa: PA, pb: PB) -> impl Parser<IA, RB, OA, E>
+pub const fn many_till<PA, PB, I, OA, OB, E>(
+    item: PA,
+    terminator: PB,
+) -> impl Parser<I, I, (Vec<OA>, OB), E>
 where
-    PA: Parser<IA, RA, OA, E>,
-    PB: Parser<RA, RB, OB, E>,
+    PA: Parser<I, I, OA, E>,
+    PB: Parser<I, I, OB, E>,
+    I: Clone,
 {
-    map(and(pa, pb), |(a, _)| a)
+    move |input: I| {
+        let mut rem = input;
+        let mut res = Vec::new();
+
+        loop {
+            if let Ok((new_rem, end)) = terminator.parse(rem.clone()) {
+                return Ok((new_rem, (res, end)));
+            }
+
+            let (new_rem, out) = item.parse(rem)?;
+            rem = new_rem;
+            res.push(out);
+        }
+    }
 }
 
-/// Combination of the right, middle and left parser.
-///
-/// Combination of the right and left parser such that given parser A B C it returns the result
-/// of B and the output of C or an exception of type E given an input valid for A.
+/// Applies an element parser 0 or more time, separated by a separator parser, keeping both.
 ///
 /// # Result Conditions
-/// All three parsers must succeed.
+/// Always succeeds. Returns as soon as either parser fails, a separator not followed by an
+/// element is not consumed. A non-empty list has one separator less than it has elements.
 ///
 /// # Example
 /// ```
 /// use parser_combinator::parsers::*;
 ///
-/// let parser = middle(whitespace, identifier, whitespace);
-/// assert_eq!(Ok(("", "ident")), parser.parse("\n ident \r"));
+/// let parser = alternating(identifier, whitespace);
+/// assert_eq!(
+///     Ok((" ", (vec!("a", "b"), vec!("  ")))),
+///     parser.parse("a  b ")
+/// );
+/// assert_eq!(Ok(("", (vec!(), vec!()))), parser.parse(""));
 /// ```
-pub const fn middle<PA, IA, RA, OA, PB, RB, OB, PC, RC, OC, E>(
-    pa: PA,
-    pb: PB,
-    pc: PC,
-) -> impl Parser<IA, RC, OB, E>
+pub const fn alternating<P, S, I, O, SO, E>(
+    elem: P,
+    sep: S,
+) -> impl Parser<I, I, (Vec<O>, Vec<SO>), E>
 where
-    PA: Parser<IA, RA, OA, E>,
-    PB: Parser<RA, RB, OB, E>,
-    PC: Parser<RB, RC, OC, E>,
+    P: Parser<I, I, O, E>,
+    S: Parser<I, I, SO, E>,
+    I: Clone,
 {
-    let p = map(and(pa, pb), |(_, b)| b);
-    map(and(p, pc), |(m, _)| m)
+    move |input: I| {
+        let mut elems = Vec::new();
+        let mut seps = Vec::new();
+
+        let mut rem = match elem.parse(input.clone()) {
+            Ok((rem, out)) => {
+                elems.push(out);
+                rem
+            }
+            Err(_) => return Ok((input, (elems, seps))),
+        };
+
+        while let Ok((sep_rem, sep_out)) = sep.parse(rem.clone()) {
+            match elem.parse(sep_rem) {
+                Ok((new_rem, out)) => {
+                    rem = new_rem;
+                    seps.push(sep_out);
+                    elems.push(out);
+                }
+                Err(_) => break,
+            }
+        }
+        Ok((rem, (elems, seps)))
+    }
 }
 
-/// Applies a parser 0 or more time. Always succeeds.
+/// Folds 1 or more elements separated by a separator, starting the accumulator from the first.
 ///
 /// # Result Conditions
-/// Always succeeds. Returns as soon as the given parser fails.
+/// Fails if the first element fails. Returns as soon as either parser fails afterward, a
+/// separator not followed by an element is not consumed.
 ///
 /// # Example
 /// ```
 /// use parser_combinator::parsers::*;
 ///
-/// let parser = while_(left(identifier, whitespace));
-/// assert_eq!(Ok(("", vec!("ident1", "ident2"))), parser.parse("ident1 ident2 "));
-/// assert_eq!(Ok(("", vec!())), parser.parse(""));
+/// let parser = sep_fold1(identifier, literal("."), |o: &str| o.to_string(), |a, o| {
+///     format!("{}::{}", a, o)
+/// });
+/// assert_eq!(Ok(("", "a::b::c".to_string())), parser.parse("a.b.c"));
+/// assert!(parser.parse("").is_err());
 /// ```
-pub const fn while_<P, I, O, E>(parser: P) -> impl Parser<I, I, Vec<O>, E>
+pub const fn sep_fold1<P, S, I, O, SO, E, A, INIT, FN>(
+    elem: P,
+    sep: S,
+    first: INIT,
+    combine: FN,
+) -> impl Parser<I, I, A, E>
 where
     P: Parser<I, I, O, E>,
+    S: Parser<I, I, SO, E>,
+    INIT: Fn(O) -> A,
+    FN: Fn(A, O) -> A,
     I: Clone,
 {
     move |input: I| {
-        let mut rem = input;
-        let mut res = Vec::new();
+        let (mut rem, out) = elem.parse(input)?;
+        let mut acc = first(out);
 
-        loop {
-            match parser.parse(rem.clone()) {
+        while let Ok((sep_rem, _)) = sep.parse(rem.clone()) {
+            match elem.parse(sep_rem) {
                 Ok((new_rem, out)) => {
                     rem = new_rem;
-                    res.push(out)
-                }
-                Err(_) => {
-                    break;
+                    acc = combine(acc, out);
                 }
+                Err(_) => break,
             }
         }
-        Ok((rem, res))
+        Ok((rem, acc))
     }
 }
 
-/// Applies a parser 1 or more time. Stops when the parser fails.
+/// Counts how many times a prefix parser applies before a body parser.
 ///
 /// # Result Conditions
-/// Succeed if the first parse succeeds and returns as soon as the given parser fails afterward.
+/// The prefix may match 0 or more times, the body must then succeed. Returns the number of
+/// prefixes matched alongside the output of the body.
 ///
 /// # Example
 /// ```
 /// use parser_combinator::parsers::*;
 ///
-/// let parser = one_or_more(left(identifier, whitespace));
-/// assert_eq!(Ok(("", vec!("ident1", "ident2"))), parser.parse("ident1 ident2 "));
-/// assert!(parser.parse("").is_err());
+/// let parser = preceded_count(literal("#"), right(whitespace, identifier));
+/// assert_eq!(Ok(("", (3, "Title"))), parser.parse("### Title"));
+/// assert_eq!(Ok(("", (0, "Title"))), parser.parse(" Title"));
+/// assert!(parser.parse("###").is_err());
 /// ```
-pub const fn one_or_more<P, I, O, E>(parser: P) -> impl Parser<I, I, Vec<O>, E>
+pub const fn preceded_count<PA, PB, I, R, OA, OB, E>(
+    prefix: PA,
+    body: PB,
+) -> impl Parser<I, R, (usize, OB), E>
 where
-    P: Parser<I, I, O, E>,
+    PA: Parser<I, I, OA, E>,
+    PB: Parser<I, R, OB, E>,
     I: Clone,
 {
     move |input: I| {
         let mut rem = input;
-        let mut res = Vec::new();
+        let mut count = 0;
 
-        let (first_rem, first_out) = parser.parse(rem.clone())?;
-        res.push(first_out);
-        rem = first_rem;
-
-        loop {
-            match parser.parse(rem.clone()) {
-                Ok((new_rem, out)) => {
-                    rem = new_rem;
-                    res.push(out)
-                }
-                Err(_) => {
-                    break;
-                }
-            }
+        while let Ok((new_rem, _)) = prefix.parse(rem.clone()) {
+            rem = new_rem;
+            count += 1;
         }
-        Ok((rem, res))
+
+        let (rem, out) = body.parse(rem)?;
+        Ok((rem, (count, out)))
     }
 }
 
@@ -372,6 +1447,35 @@ where
     }
 }
 
+/// Applies the parser and checks its output against the predicate.
+///
+/// # Result Conditions
+/// Fails if the parser fails, or with the given error if the predicate is false. The predicate
+/// receives the output of the parser, unlike `parse_if` whose predicate receives the input
+/// before it is parsed.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+/// use parser_combinator::regexes::*;
+///
+/// let port = map(matching(&JSON_NUMBER_REGEX), |n: &str| n.parse::<f64>().unwrap());
+/// let parser = verify(port, |n| (1.0..65536.0).contains(n), "Invalid port".to_string());
+/// assert_eq!(Ok(("", 8080.0)), parser.parse("8080"));
+/// assert_eq!(Err("Invalid port".to_string()), parser.parse("70000"));
+/// ```
+pub const fn verify<P, I, R, O, E, FN>(parser: P, pred: FN, error: E) -> impl Parser<I, R, O, E>
+where
+    P: Parser<I, R, O, E>,
+    FN: Fn(&O) -> bool,
+    E: Clone,
+{
+    move |input: I| match parser.parse(input)? {
+        (rem, out) if pred(&out) => Ok((rem, out)),
+        _ => Err(error.clone()),
+    }
+}
+
 /// Used for debugging, will call the given closure before applying the parser.
 ///
 /// # Result Condition
@@ -399,6 +1503,42 @@ where
     }
 }
 
+/// Used for instrumentation, will call the given closure with the result of the parser.
+///
+/// # Result Condition
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+/// use std::cell::Cell;
+///
+/// let (successes, failures) = (Cell::new(0), Cell::new(0));
+/// let parser = tap_result(identifier, |res| match res {
+///     Ok(_) => successes.set(successes.get() + 1),
+///     Err(_) => failures.set(failures.get() + 1),
+/// });
+///
+/// assert!(parser.parse("foo").is_ok());
+/// assert!(parser.parse("bar").is_ok());
+/// assert!(parser.parse("123").is_err());
+/// assert_eq!((2, 1), (successes.get(), failures.get()));
+/// ```
+///
+/// # Note
+/// Leaves the logic of the given parser untouched.
+pub const fn tap_result<P, I, R, O, E, FN>(parser: P, func: FN) -> impl Parser<I, R, O, E>
+where
+    FN: Fn(&Result<(R, O), E>),
+    P: Parser<I, R, O, E>,
+{
+    move |input: I| {
+        let res = parser.parse(input);
+        func(&res);
+        res
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::base_parsers::*;
@@ -410,6 +1550,19 @@ mod test {
         assert_eq!(Ok(("World", ("Hello", " "))), parser.parse("Hello World"));
     }
 
+    #[test]
+    fn test_and_then_parser() {
+        let digit = map(take_while1(|c| c.is_ascii_digit()), |n: &str| {
+            n.parse::<usize>().unwrap()
+        });
+        let parser = and_then(digit, |n| count(right(whitespace, identifier), n));
+
+        assert_eq!(Ok((" d", vec!("a", "b", "c"))), parser.parse("3 a b c d"));
+        assert_eq!(Ok((" a", vec!())), parser.parse("0 a"));
+        assert!(parser.parse("2 a").is_err());
+        assert!(parser.parse("a b").is_err());
+    }
+
     #[test]
     fn test_map_parser() {
         let parser = and(identifier, whitespace);
@@ -417,6 +1570,96 @@ mod test {
         assert_eq!(Ok(("World", " ")), parser.parse("Hello World"));
     }
 
+    #[test]
+    fn test_map_res_parser() {
+        let digits = take_while1(|c| c.is_ascii_digit());
+        let port = map_res(digits, |n: &str| match n.parse::<u16>() {
+            Ok(0) => Err("Port 0 is reserved".to_string()),
+            Ok(port) => Ok(port),
+            Err(_) => Err(format!("Port {} is out of range", n)),
+        });
+        let parser = right(literal(":"), port);
+
+        assert_eq!(Ok(("/", 8080)), parser.parse(":8080/"));
+        assert_eq!(Err("Port 0 is reserved".to_string()), parser.parse(":0"));
+        assert_eq!(
+            Err("Port 65536 is out of range".to_string()),
+            parser.parse(":65536")
+        );
+        assert_eq!(
+            Err("Could not parse ':'".to_string()),
+            parser.parse("8080")
+        );
+    }
+
+    #[test]
+    fn test_map_err_parser() {
+        let parser = map_err(identifier, |err: String| err.starts_with("Could not parse"));
+        assert_eq!(Ok((" World", "Hello")), parser.parse("Hello World"));
+        assert_eq!(Err(true), parser.parse("12345"));
+    }
+
+    #[test]
+    fn test_map_err_with_input_parser() {
+        let parser = map_err_with_input(identifier, |input: &str, err| {
+            format!("{} at '{}'", err, input)
+        });
+        assert_eq!(Ok((" World", "Hello")), parser.parse("Hello World"));
+        assert_eq!(
+            Err(String::from(
+                "Could not parse '\\A[a-zA-Z_][a-zA-Z0-9_]*' at '12345'"
+            )),
+            parser.parse("12345")
+        );
+    }
+
+    #[test]
+    fn test_choice_parser() {
+        let keywords = [literal("let"), literal("fn"), literal("if")];
+        let parser = choice(&keywords);
+        assert_eq!(Ok((" x", "fn")), parser.parse("fn x"));
+        assert_eq!(Ok(("", "if")), parser.parse("if"));
+        assert_eq!(Err(String::from("Could not parse 'if'")), parser.parse("else"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_choice_parser_empty() {
+        choice::<fn(&str) -> Result<(&str, ()), String>, _, _, _, _>(&[]);
+    }
+
+    #[test]
+    fn test_peek_parser() {
+        let parser = and(peek(literal("{")), identifier);
+        assert!(parser.parse("{a").is_err());
+
+        let parser = and(peek(identifier), left(identifier, whitespace));
+        assert_eq!(Ok(("b", ("a", "a"))), parser.parse("a b"));
+    }
+
+    #[test]
+    fn test_if_peek_parser() {
+        let flag = right(literal("-"), identifier);
+        let parser = if_peek(literal("-"), flag, not_whitespace);
+        assert_eq!(Ok(("", "flag")), parser.parse("-flag"));
+        assert_eq!(Ok(("", "word")), parser.parse("word"));
+
+        // The fallback would match but is not attempted once the condition holds
+        assert!(parser.parse("-").is_err());
+    }
+
+    #[test]
+    fn test_parser_ext() {
+        let chained = identifier.left(whitespace).and(identifier).map(|(a, b)| (b, a));
+        let nested = map(and(left(identifier, whitespace), identifier), |(a, b)| (b, a));
+        for input in ["Hello World", "Hello", "Hello 12"] {
+            assert_eq!(nested.parse(input), chained.parse(input));
+        }
+
+        let parser = whitespace.right(identifier).many();
+        assert_eq!(Ok(("", vec!("a", "b"))), parser.parse(" a b"));
+    }
+
     #[test]
     fn test_right_parser() {
         let parser = right(identifier, whitespace);
@@ -438,6 +1681,22 @@ mod test {
         // Second succeed
         let parser = or(identifier, whitespace);
         assert_eq!(Ok(("Hello World", " ")), parser.parse(" Hello World"));
+
+        // Both fail, the error of the second one is kept
+        let parser = or(literal("a"), literal("b"));
+        assert_eq!(Err("Could not parse 'b'".to_string()), parser.parse("c"));
+    }
+
+    #[test]
+    fn test_or_merge_parser() {
+        let keyword = or_merge(literal("let"), literal("fn"), |a: String, b| a + ", " + &b);
+        let parser = or_merge(keyword, identifier, |a, b| format!("Expected {}, {}", a, b));
+        assert_eq!(Ok((" x", "let")), parser.parse("let x"));
+        assert_eq!(Ok(("", "x")), parser.parse("x"));
+
+        let err = parser.parse("1").unwrap_err();
+        assert!(err.contains("'let'") && err.contains("'fn'"), "{}", err);
+        assert!(err.contains("[a-zA-Z_]"), "{}", err);
     }
 
     #[test]
@@ -453,6 +1712,19 @@ mod test {
         assert_eq!(Ok(("12345", vec!())), parser.parse("12345"));
     }
 
+    #[test]
+    fn test_while_capacity_parser() {
+        let source = "x ".repeat(10_000);
+        let parser = while_capacity(left(literal("x"), whitespace), 10_000);
+        let (rem, xs) = parser.parse(&source).unwrap();
+
+        assert_eq!("", rem);
+        assert_eq!(10_000, xs.len());
+        assert_eq!(10_000, xs.capacity());
+        assert!(xs.iter().all(|x| *x == "x"));
+        assert_eq!(Ok(("y", vec!())), parser.parse("y"));
+    }
+
     #[test]
     fn test_one_or_more_parser() {
         let parser = left(identifier, whitespace);
@@ -483,6 +1755,167 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_alternating_parser() {
+        let separator = regex::Regex::new(r"\A\s*,\s*").unwrap();
+        let parser = alternating(identifier, matching(&separator));
+        assert_eq!(
+            Ok(("", (vec!("a", "b", "c"), vec!(", ", " ,")))),
+            parser.parse("a, b ,c")
+        );
+
+        // The dangling separator is left in the remainder
+        assert_eq!(Ok((", 1", (vec!("a"), vec!()))), parser.parse("a, 1"));
+    }
+
+    #[test]
+    fn test_fold_many_parsers() {
+        let count = fold_many0(left(identifier, maybe(whitespace)), 0, |n, _| n + 1);
+        assert_eq!(Ok(("1", 3)), count.parse("a b c 1"));
+        // The accumulator starts over on each parse
+        assert_eq!(Ok(("", 1)), count.parse("a"));
+
+        let longest = fold_many1(left(identifier, maybe(whitespace)), "", |a, b| {
+            std::cmp::max_by_key(a, b, |s: &&str| s.len())
+        });
+        assert_eq!(Ok(("", "World")), longest.parse("Hello World x"));
+        assert!(longest.parse("").is_err());
+    }
+
+    #[test]
+    fn test_skip_many_parsers() {
+        let parser = right(skip_many(whitespace), identifier);
+        assert_eq!(Ok(("", "a")), parser.parse(" \t\n a"));
+        assert_eq!(Ok(("", "a")), parser.parse("a"));
+
+        let words = skip_many(left(identifier, maybe(whitespace)));
+        assert_eq!(Ok(("1", ())), words.parse("a b c 1"));
+
+        let parser = skip_many1(whitespace);
+        assert_eq!(Ok(("a", ())), parser.parse("  a"));
+        assert!(parser.parse("a").is_err());
+    }
+
+    #[test]
+    fn test_many_chars_parser() {
+        // Escaped characters are unescaped on the fly
+        let escaped = right(literal("\\"), one_of("\\\""));
+        let parser = many_chars(or(escaped, none_of("\\\"")));
+        assert_eq!(
+            Ok(("\"", "a\"b\\é".to_string())),
+            parser.parse("a\\\"b\\\\é\"")
+        );
+        // The string starts over on each parse
+        assert_eq!(Ok(("", "x".to_string())), parser.parse("x"));
+    }
+
+    #[test]
+    fn test_collect_many_parser() {
+        use std::collections::HashSet;
+
+        let idents = collect_many(left(identifier, maybe(whitespace)));
+        let (rem, set): (_, HashSet<&str>) = idents.parse("a b a c b 1").unwrap();
+        assert_eq!("1", rem);
+        assert_eq!(HashSet::from(["a", "b", "c"]), set);
+
+        let chars = collect_many(satisfy(char::is_alphabetic));
+        assert_eq!(Ok(("1", "abé".to_string())), chars.parse("abé1"));
+        assert_eq!(Ok(("1", String::new())), chars.parse("1"));
+    }
+
+    #[test]
+    fn test_chainl1_calculator() {
+        let number = map(take_while1(|c| c.is_ascii_digit()), |n: &str| {
+            n.parse::<i64>().unwrap()
+        });
+        let operand = middle(maybe(whitespace), number, maybe(whitespace));
+        let plus = map(literal("+"), |_| (|a, b| a + b) as fn(i64, i64) -> i64);
+        let minus = map(literal("-"), |_| (|a, b| a - b) as fn(i64, i64) -> i64);
+        let parser = chainl1(operand, or(plus, minus));
+
+        assert_eq!(Ok(("", 42)), parser.parse("42"));
+        assert_eq!(Ok(("", -4)), parser.parse("1 - 2 - 3"));
+        assert_eq!(Ok(("", 6)), parser.parse("10 - 5 + 1"));
+        assert_eq!(Ok(("", 2)), parser.parse("10 - 5 - 4 + 1"));
+        assert_eq!(Ok(("+ x", 3)), parser.parse("1 + 2 + x"));
+        assert!(parser.parse("- 1").is_err());
+    }
+
+    #[test]
+    fn test_sep_by_parser() {
+        let separator = middle(maybe(whitespace), literal(","), maybe(whitespace));
+        let parser = sep_by(identifier, separator);
+        assert_eq!(Ok(("]", vec!("a", "b"))), parser.parse("a , b]"));
+        assert_eq!(Ok(("]", vec!())), parser.parse("]"));
+
+        // A trailing separator is left in the remainder
+        assert_eq!(Ok((", ]", vec!("a", "b"))), parser.parse("a, b, ]"));
+    }
+
+    #[test]
+    fn test_sep_by1_parser() {
+        let parser = sep_by1(identifier, literal(","));
+        assert_eq!(Ok((" c", vec!("a", "b"))), parser.parse("a,b c"));
+        assert_eq!(
+            Err(String::from("Could not parse '\\A[a-zA-Z_][a-zA-Z0-9_]*'")),
+            parser.parse(",a")
+        );
+    }
+
+    #[test]
+    fn test_count_parser() {
+        let parser = count(left(identifier, maybe(whitespace)), 3);
+        assert_eq!(Ok(("d", vec!("a", "b", "c"))), parser.parse("a b c d"));
+        assert_eq!(
+            Err(String::from("Could not parse '\\A[a-zA-Z_][a-zA-Z0-9_]*'")),
+            parser.parse("a b 1")
+        );
+
+        let parser = count(identifier, 0);
+        assert_eq!(Ok(("abc", vec!())), parser.parse("abc"));
+    }
+
+    #[test]
+    fn test_many_m_n_parser() {
+        let parser = many_m_n(1, 2, left(identifier, maybe(whitespace)));
+        assert_eq!(Ok(("c", vec!("a", "b"))), parser.parse("a b c"));
+        assert_eq!(Ok(("1", vec!("a"))), parser.parse("a 1"));
+        assert!(parser.parse("1").is_err());
+
+        // Equivalent to count
+        let parser = many_m_n(2, 2, left(identifier, maybe(whitespace)));
+        assert_eq!(Ok(("c", vec!("a", "b"))), parser.parse("a b c"));
+        assert!(parser.parse("a 1").is_err());
+
+        let parser = many_m_n(0, 0, identifier);
+        assert_eq!(Ok(("abc", vec!())), parser.parse("abc"));
+    }
+
+    #[test]
+    fn test_many_till_parser() {
+        let parser = many_till(left(identifier, whitespace), literal("end"));
+        assert_eq!(Ok((";", (vec!("a", "b"), "end"))), parser.parse("a b end;"));
+        assert_eq!(Ok(("", (vec!(), "end"))), parser.parse("end"));
+
+        // Inputs missing the terminator fail
+        assert!(parser.parse("a b 1").is_err());
+        assert!(parser.parse("a b ").is_err());
+    }
+
+    #[test]
+    fn test_sep_fold1_parser() {
+        let number = map(matching(&crate::regexes::JSON_NUMBER_REGEX), |n: &str| {
+            n.parse::<i64>().unwrap()
+        });
+        let separator = middle(maybe(whitespace), literal(","), maybe(whitespace));
+        let parser = sep_fold1(number, separator, |n| n, i64::max);
+
+        assert_eq!(Ok(("", 7)), parser.parse("3, 7 ,-2,5"));
+        assert_eq!(Ok(("", -4)), parser.parse("-4"));
+        assert_eq!(Ok((", x", 2)), parser.parse("1, 2, x"));
+        assert!(parser.parse("").is_err());
+    }
+
     #[test]
     fn test_parse_if_parser() {
         let parser = left(identifier, whitespace);
@@ -491,4 +1924,79 @@ mod test {
 
         assert_eq!(Ok(("Bye World", None)), parser.parse("Bye World"));
     }
+
+    #[test]
+    fn test_verify_parser() {
+        let parser = verify(identifier, |i| i.len() <= 3, String::from("Too long"));
+        assert_eq!(Ok((" b", "abc")), parser.parse("abc b"));
+        assert_eq!(Err(String::from("Too long")), parser.parse("abcd"));
+        assert_eq!(
+            Err(String::from("Could not parse '\\A[a-zA-Z_][a-zA-Z0-9_]*'")),
+            parser.parse("1")
+        );
+    }
+
+    #[test]
+    fn test_preceded_count_parser() {
+        let parser = preceded_count(whitespace_no_newline, identifier);
+        assert_eq!(Ok(("", (1, "Hello"))), parser.parse("    Hello"));
+
+        let parser = preceded_count(literal("> "), identifier);
+        assert_eq!(Ok((" World", (2, "Hello"))), parser.parse("> > Hello World"));
+        assert_eq!(Ok((" World", (0, "Hello"))), parser.parse("Hello World"));
+        assert!(parser.parse("> > 12345").is_err());
+    }
+
+    #[test]
+    fn test_sequence_of_parser() {
+        let keywords = ["let", " ", "x"];
+        let parsers = keywords
+            .iter()
+            .map(|k| Box::new(literal(*k)) as Box<dyn Parser<&str, &str, &str, String>>)
+            .collect();
+
+        let parser = sequence_of(parsers);
+        assert_eq!(
+            Ok((" = 5", vec!("let", " ", "x"))),
+            parser.parse("let x = 5")
+        );
+        assert!(parser.parse("let y = 5").is_err());
+
+        let parser = sequence_of::<&str, &str, String>(vec!());
+        assert_eq!(Ok(("let", vec!())), parser.parse("let"));
+    }
+
+    /// Nested lists of identifiers, such as `(a (b c) ())`.
+    #[derive(Debug, PartialEq)]
+    enum Tree<'a> {
+        Leaf(&'a str),
+        Node(Vec<Tree<'a>>),
+    }
+
+    fn tree<'a>() -> BoxedParser<'a, &'a str, &'a str, Tree<'a>, String> {
+        let node = middle(literal("("), while_(|input| tree().parse(input)), literal(")"));
+        let node = map(node, Tree::Node);
+        left(or(map(identifier, Tree::Leaf), node), maybe(whitespace)).boxed()
+    }
+
+    #[test]
+    fn test_boxed_recursive_grammar() {
+        use Tree::*;
+
+        assert_eq!(
+            Ok(("", Node(vec!(Leaf("a"), Node(vec!(Leaf("b"), Leaf("c"))), Node(vec!()))))),
+            tree().parse("(a (b c) ())")
+        );
+        assert!(tree().parse("(a (b)").is_err());
+
+        let deep = format!("{}x{}", "(".repeat(200), ")".repeat(200));
+        assert!(tree().parse(&deep).is_ok());
+    }
+
+    #[test]
+    fn test_boxed_mixes_parser_types() {
+        let parsers = [boxed(identifier), boxed(literal("--")), whitespace.boxed()];
+        let parser = while_(choice(&parsers));
+        assert_eq!(Ok(("!", vec!("a", " ", "--", "b"))), parser.parse("a --b!"));
+    }
 }
@@ -0,0 +1,187 @@
+use crate::parsers::*;
+use std::marker::PhantomData;
+use std::ops::{Add, BitOr, Shl, Shr};
+
+/// Signature of a parser, only used to carry its types.
+type Signature<I, R, O, E> = fn(I) -> Result<(R, O), E>;
+
+/// Wrapper around a parser allowing grammars to be written with operators.
+///
+/// | Operator | Combinator |
+/// |----------|------------|
+/// | `a \| b` | `or(a, b)` |
+/// | `a + b`  | `and(a, b)` |
+/// | `a >> b` | `right(a, b)` |
+/// | `a << b` | `left(a, b)` |
+///
+/// Both operands must be wrapped, the types of a parser can not be inferred otherwise.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = comb(identifier) >> comb(whitespace) | comb(literal("--"));
+/// assert_eq!(Ok(("", " ")), parser.parse("ident "));
+/// assert_eq!(Ok(("", "--")), parser.parse("--"));
+/// ```
+///
+/// # Note
+/// Operator precedence is the one of Rust: `+` binds tighter than `>>` and `<<`, which bind
+/// tighter than `|`. Prefer parentheses when mixing `+` with shifts.
+pub struct Comb<P, I, R, O, E> {
+    parser: P,
+    types: PhantomData<Signature<I, R, O, E>>,
+}
+
+/// Wraps a parser so that it can be combined with operators.
+pub const fn comb<P, I, R, O, E>(parser: P) -> Comb<P, I, R, O, E>
+where
+    P: Parser<I, R, O, E>,
+{
+    Comb {
+        parser,
+        types: PhantomData,
+    }
+}
+
+impl<P, I, R, O, E> Parser<I, R, O, E> for Comb<P, I, R, O, E>
+where
+    P: Parser<I, R, O, E>,
+{
+    fn parse(&self, input: I) -> Result<(R, O), E> {
+        self.parser.parse(input)
+    }
+}
+
+/// Parser built by the `|` operator on [`Comb`].
+pub struct CombOr<A, B>(A, B);
+
+/// Parser built by the `+` operator on [`Comb`].
+pub struct CombAnd<A, B>(A, B);
+
+/// Parser built by the `<<` operator on [`Comb`].
+pub struct CombLeft<A, B>(A, B);
+
+/// Parser built by the `>>` operator on [`Comb`].
+pub struct CombRight<A, B>(A, B);
+
+impl<PA, PB, I, R, O, E> Parser<I, R, O, E> for CombOr<Comb<PA, I, R, O, E>, Comb<PB, I, R, O, E>>
+where
+    PA: Parser<I, R, O, E>,
+    PB: Parser<I, R, O, E>,
+    I: Clone,
+{
+    fn parse(&self, input: I) -> Result<(R, O), E> {
+        or(|i| self.0.parse(i), |i| self.1.parse(i)).parse(input)
+    }
+}
+
+impl<PA, PB, IA, RA, OA, RB, OB, E> Parser<IA, RB, (OA, OB), E>
+    for CombAnd<Comb<PA, IA, RA, OA, E>, Comb<PB, RA, RB, OB, E>>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    fn parse(&self, input: IA) -> Result<(RB, (OA, OB)), E> {
+        and(|i| self.0.parse(i), |i| self.1.parse(i)).parse(input)
+    }
+}
+
+impl<PA, PB, IA, RA, OA, RB, OB, E> Parser<IA, RB, OA, E>
+    for CombLeft<Comb<PA, IA, RA, OA, E>, Comb<PB, RA, RB, OB, E>>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    fn parse(&self, input: IA) -> Result<(RB, OA), E> {
+        left(|i| self.0.parse(i), |i| self.1.parse(i)).parse(input)
+    }
+}
+
+impl<PA, PB, IA, RA, OA, RB, OB, E> Parser<IA, RB, OB, E>
+    for CombRight<Comb<PA, IA, RA, OA, E>, Comb<PB, RA, RB, OB, E>>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    fn parse(&self, input: IA) -> Result<(RB, OB), E> {
+        right(|i| self.0.parse(i), |i| self.1.parse(i)).parse(input)
+    }
+}
+
+impl<PA, PB, I, R, O, E> BitOr<Comb<PB, I, R, O, E>> for Comb<PA, I, R, O, E>
+where
+    PA: Parser<I, R, O, E>,
+    PB: Parser<I, R, O, E>,
+    I: Clone,
+{
+    type Output = Comb<CombOr<Self, Comb<PB, I, R, O, E>>, I, R, O, E>;
+
+    fn bitor(self, rhs: Comb<PB, I, R, O, E>) -> Self::Output {
+        comb(CombOr(self, rhs))
+    }
+}
+
+impl<PA, PB, IA, RA, OA, RB, OB, E> Add<Comb<PB, RA, RB, OB, E>> for Comb<PA, IA, RA, OA, E>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    type Output = Comb<CombAnd<Self, Comb<PB, RA, RB, OB, E>>, IA, RB, (OA, OB), E>;
+
+    fn add(self, rhs: Comb<PB, RA, RB, OB, E>) -> Self::Output {
+        comb(CombAnd(self, rhs))
+    }
+}
+
+impl<PA, PB, IA, RA, OA, RB, OB, E> Shl<Comb<PB, RA, RB, OB, E>> for Comb<PA, IA, RA, OA, E>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    type Output = Comb<CombLeft<Self, Comb<PB, RA, RB, OB, E>>, IA, RB, OA, E>;
+
+    fn shl(self, rhs: Comb<PB, RA, RB, OB, E>) -> Self::Output {
+        comb(CombLeft(self, rhs))
+    }
+}
+
+impl<PA, PB, IA, RA, OA, RB, OB, E> Shr<Comb<PB, RA, RB, OB, E>> for Comb<PA, IA, RA, OA, E>
+where
+    PA: Parser<IA, RA, OA, E>,
+    PB: Parser<RA, RB, OB, E>,
+{
+    type Output = Comb<CombRight<Self, Comb<PB, RA, RB, OB, E>>, IA, RB, OB, E>;
+
+    fn shr(self, rhs: Comb<PB, RA, RB, OB, E>) -> Self::Output {
+        comb(CombRight(self, rhs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comb_operators() {
+        let key_value = comb(identifier) << (comb(literal("=")) + comb(identifier));
+        assert_eq!(Ok((";", "key")), key_value.parse("key=value;"));
+
+        let pair = (comb(identifier) + comb(whitespace)) >> comb(identifier);
+        assert_eq!(Ok(("", "b")), pair.parse("a b"));
+
+        let flag = comb(literal("--")) >> comb(identifier) | comb(identifier);
+        assert_eq!(Ok(("", "verbose")), flag.parse("--verbose"));
+        assert_eq!(Ok(("", "file")), flag.parse("file"));
+        assert!(flag.parse("--").is_err());
+    }
+
+    #[test]
+    fn test_comb_same_as_functions() {
+        let with_ops = comb(identifier) + comb(whitespace);
+        let with_fns = and(identifier, whitespace);
+        for input in ["Hello World", "Hello", "12"] {
+            assert_eq!(with_fns.parse(input), with_ops.parse(input));
+        }
+    }
+}
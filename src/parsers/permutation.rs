@@ -0,0 +1,126 @@
+use crate::parsers::*;
+
+/// Tuple of parsers that can be applied in any order, see [`permutation`].
+///
+/// Implemented for tuples of 2 to 6 parsers sharing input and error types.
+pub trait Permutation<I, O, E> {
+    /// Applies each parser of the tuple exactly once, in whichever order the input calls for.
+    fn permute(&self, input: I) -> Result<(I, O), E>;
+}
+
+/// Implements [`Permutation`] for a tuple, given a parser type, its output type, the slot
+/// holding its output and its index in the tuple for each member.
+macro_rules! permutation_tuple {
+    ($($parser:ident $out:ident $slot:ident $idx:tt),+) => {
+        impl<I, E, $($parser, $out),+> Permutation<I, ($($out,)+), E> for ($($parser,)+)
+        where
+            I: Clone,
+            $($parser: Parser<I, I, $out, E>),+
+        {
+            fn permute(&self, input: I) -> Result<(I, ($($out,)+)), E> {
+                let mut rem = input;
+                $(let mut $slot = None;)+
+
+                // Tries the parsers yet to match in order, starting over after each match
+                loop {
+                    let mut last_err = None;
+                    $(
+                        if $slot.is_none() {
+                            match self.$idx.parse(rem.clone()) {
+                                Ok((new_rem, out)) => {
+                                    rem = new_rem;
+                                    $slot = Some(out);
+                                    continue;
+                                }
+                                Err(err) => last_err = Some(err),
+                            }
+                        }
+                    )+
+
+                    match last_err {
+                        Some(err) => return Err(err),
+                        None => break,
+                    }
+                }
+
+                // No parser failed the last round, so all of them matched
+                Ok((rem, ($($slot.unwrap(),)+)))
+            }
+        }
+    };
+}
+
+permutation_tuple!(PA OA a 0, PB OB b 1);
+permutation_tuple!(PA OA a 0, PB OB b 1, PC OC c 2);
+permutation_tuple!(PA OA a 0, PB OB b 1, PC OC c 2, PD OD d 3);
+permutation_tuple!(PA OA a 0, PB OB b 1, PC OC c 2, PD OD d 3, PE OE e 4);
+permutation_tuple!(PA OA a 0, PB OB b 1, PC OC c 2, PD OD d 3, PE OE e 4, PF OF f 5);
+
+/// Takes a tuple of parsers and applies each of them exactly once, in any order.
+///
+/// Returns their outputs in the order of the tuple, whatever the order they matched in.
+///
+/// # Result Conditions
+/// Fails if a parser never matches, with the error of the last parser tried.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let a = token(literal::<_, String>("a"));
+/// let parser = permutation((a, token(literal("b")), token(literal("c"))));
+/// assert_eq!(Ok(("", ("a", "b", "c"))), parser.parse("c a b"));
+/// assert_eq!(Ok(("", ("a", "b", "c"))), parser.parse("a b c"));
+/// assert!(parser.parse("a c").is_err());
+/// assert!(parser.parse("a a b c").is_err());
+/// ```
+pub const fn permutation<T, I, O, E>(parsers: T) -> impl Parser<I, I, O, E>
+where
+    T: Permutation<I, O, E>,
+{
+    move |input: I| parsers.permute(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Header attributes separated by `;`, in any order.
+    fn attributes(input: &str) -> Result<(&str, (&str, bool, &str)), String> {
+        let separator = |input| maybe(token(literal(";"))).parse(input);
+        let key = right(literal("key="), identifier);
+        let flag = map(literal("flag"), |_| true);
+        let name = right(literal("name="), identifier);
+
+        let parser = permutation((
+            left(key, separator),
+            left(flag, separator),
+            left(name, separator),
+        ));
+        parser.parse(input)
+    }
+
+    #[test]
+    fn test_permutation_orders() {
+        let expected = Ok(("", ("val", true, "x")));
+        assert_eq!(expected, attributes("key=val; flag; name=x"));
+        assert_eq!(expected, attributes("name=x; key=val; flag"));
+        assert_eq!(expected, attributes("flag; name=x; key=val"));
+    }
+
+    #[test]
+    fn test_permutation_missing_member() {
+        assert_eq!(
+            Err("Could not parse 'name='".to_string()),
+            attributes("key=val; flag")
+        );
+        assert!(attributes("key=val; flag; key=other").is_err());
+        assert!(attributes("").is_err());
+    }
+
+    #[test]
+    fn test_permutation_leaves_remainder() {
+        let parser = permutation((literal::<_, String>("x"), literal("y")));
+        assert_eq!(Ok(("x", ("x", "y"))), parser.parse("yxx"));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::parsers::*;
+use std::cell::RefCell;
+
+/// Out-of-band collector of the offsets at which a parse can safely be resumed.
+///
+/// Offsets are relative to the source given on creation, kept sorted and without duplicates as
+/// backtracking may reach the same recovery point several times.
+#[derive(Debug)]
+pub struct RecoveryPoints<'a> {
+    source: &'a str,
+    offsets: RefCell<Vec<usize>>,
+}
+
+impl<'a> RecoveryPoints<'a> {
+    /// Creates an empty collector for the given source.
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            offsets: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Retrieves the recorded offsets, in increasing order.
+    pub fn offsets(&self) -> Vec<usize> {
+        self.offsets.borrow().clone()
+    }
+
+    /// Retrieves the last recorded offset at or before the given one.
+    ///
+    /// Meant to find where to resume parsing given the offset of an error.
+    pub fn nearest(&self, offset: usize) -> Option<usize> {
+        let offsets = self.offsets.borrow();
+        let idx = offsets.partition_point(|&o| o <= offset);
+        idx.checked_sub(1).map(|idx| offsets[idx])
+    }
+
+    /// Records the offset of a slice of the source. Slices from another source are ignored.
+    fn record(&self, input: &str) {
+        if let Some(offset) = input.source_range_start(self.source) {
+            let mut offsets = self.offsets.borrow_mut();
+            if let Err(idx) = offsets.binary_search(&offset) {
+                offsets.insert(idx, offset);
+            }
+        }
+    }
+}
+
+/// Records the current offset as a recovery point before applying the parser.
+///
+/// Typically wraps statements or any other construct at the boundary of which a parse can be
+/// resynchronized.
+///
+/// # Result Conditions
+/// Same as input parser. The offset is recorded whether the parser succeeds or not.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let source = "foo; bar; baz";
+/// let points = RecoveryPoints::new(source);
+/// let statement = left(identifier, and(literal(";"), maybe(whitespace)));
+/// let parser = while_(recovery_point(statement, &points));
+///
+/// assert_eq!(Ok(("baz", vec!("foo", "bar"))), parser.parse(source));
+/// assert_eq!(vec![0, 5, 10], points.offsets());
+/// assert_eq!(Some(10), points.nearest(12));
+/// ```
+pub const fn recovery_point<'a, 'c, P, O>(
+    parser: P,
+    points: &'c RecoveryPoints<'a>,
+) -> impl Parser<&'a str, &'a str, O, String> + 'c
+where
+    P: Parser<&'a str, &'a str, O, String> + 'c,
+{
+    move |input: &'a str| {
+        points.record(input);
+        parser.parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_point_statements() {
+        let source = "let a; let b;\nlet ;\nlet c;";
+        let points = RecoveryPoints::new(source);
+
+        let statement = middle(
+            and(literal("let"), whitespace),
+            identifier,
+            and(literal(";"), maybe(whitespace)),
+        );
+        let parser = while_(recovery_point(statement, &points));
+
+        let (rem, names) = parser.parse(source).unwrap();
+        assert_eq!(vec!("a", "b"), names);
+        assert_eq!("let ;\nlet c;", rem);
+
+        // The failing statement is recorded too, giving where to resume from
+        assert_eq!(vec![0, 7, 14], points.offsets());
+        assert_eq!(Some(14), points.nearest(18));
+        assert_eq!(Some(7), points.nearest(7));
+    }
+
+    #[test]
+    fn test_recovery_point_backtracking() {
+        let source = "a1";
+        let points = RecoveryPoints::new(source);
+
+        let parser = or(
+            recovery_point(literal("ab"), &points),
+            recovery_point(identifier, &points),
+        );
+        assert_eq!(Ok(("", "a1")), parser.parse(source));
+        assert_eq!(vec![0], points.offsets());
+        assert_eq!(None, RecoveryPoints::new(source).nearest(0));
+
+        // Slices not coming from the source are not recorded
+        assert!(parser.parse("other").is_ok());
+        assert_eq!(vec![0], points.offsets());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::parsers::*;
+use std::cell::OnceCell;
+use std::rc::{Rc, Weak};
+
+/// Slot the definition of a recursive parser is stored in once built.
+type Definition<'p, I, R, O, E> = OnceCell<BoxedParser<'p, I, R, O, E>>;
+
+/// Parser defined in terms of itself, built by [`recursive`].
+pub struct Recursive<'p, I, R, O, E> {
+    definition: Rc<Definition<'p, I, R, O, E>>,
+}
+
+/// Handle to a [`Recursive`] parser, used within its own definition.
+///
+/// # Note
+/// The handle does not keep the parser alive, so that the definition does not own itself. Using
+/// it once the parser is dropped, or while the parser is still being defined, panics.
+pub struct RecursiveRef<'p, I, R, O, E> {
+    definition: Weak<Definition<'p, I, R, O, E>>,
+}
+
+/// Cloned so that the handle can appear several times in the definition.
+impl<I, R, O, E> Clone for RecursiveRef<'_, I, R, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            definition: self.definition.clone(),
+        }
+    }
+}
+
+impl<I, R, O, E> Parser<I, R, O, E> for Recursive<'_, I, R, O, E> {
+    fn parse(&self, input: I) -> Result<(R, O), E> {
+        let parser = self.definition.get().expect("Recursive parser is defined");
+        parser.parse(input)
+    }
+}
+
+impl<I, R, O, E> Parser<I, R, O, E> for RecursiveRef<'_, I, R, O, E> {
+    fn parse(&self, input: I) -> Result<(R, O), E> {
+        let definition = self
+            .definition
+            .upgrade()
+            .expect("Recursive parser used after being dropped");
+        let parser = definition
+            .get()
+            .expect("Recursive parser used while being defined");
+        parser.parse(input)
+    }
+}
+
+/// Defines a parser that refers to itself.
+///
+/// The closure receives a handle to the parser being defined and returns its definition. This
+/// allows writing recursive grammars as closures, where a top level function would otherwise be
+/// needed to break the type recursion.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// // Balanced parentheses, yielding their nesting depth
+/// let parens = recursive(|parens| {
+///     let nested = middle(literal::<_, String>("("), parens, literal(")"));
+///     or(map(nested, |depth: usize| depth + 1), map(nothing, |_| 0))
+/// });
+///
+/// assert_eq!(Ok(("", 3)), parens.parse("((()))"));
+/// assert_eq!(Ok((")", 1)), parens.parse("())"));
+/// assert_eq!(Ok(("((", 0)), parens.parse("(("));
+/// ```
+pub fn recursive<'p, F, P, I, R, O, E>(definition: F) -> Recursive<'p, I, R, O, E>
+where
+    F: FnOnce(RecursiveRef<'p, I, R, O, E>) -> P,
+    P: Parser<I, R, O, E> + 'p,
+{
+    let cell = Rc::new(OnceCell::new());
+    let handle = RecursiveRef {
+        definition: Rc::downgrade(&cell),
+    };
+
+    let parser = boxed(definition(handle));
+    if cell.set(parser).is_err() {
+        unreachable!("The definition is only set once");
+    }
+    Recursive { definition: cell }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recursive_lists() {
+        // Nested comma separated lists of identifiers, counting the identifiers
+        let list = recursive(|list| {
+            let nested = middle(literal("["), comma_list(list), literal("]"));
+            let nested = map(nested, |counts: Vec<usize>| counts.iter().sum());
+            or(map(identifier, |_| 1), nested)
+        });
+
+        assert_eq!(Ok(("", 4)), list.parse("[a, [b, [c]], [], d]"));
+        assert_eq!(Ok(("", 1)), list.parse("a"));
+        assert!(list.parse("[a, [b]").is_err());
+    }
+
+    #[test]
+    fn test_recursive_handle_used_twice() {
+        // Binary tree of `x` leaves, such as `(x (x x))`
+        let tree = recursive(|tree| {
+            let children = and(left(tree.clone(), whitespace), tree);
+            let node = middle(literal("("), children, literal(")"));
+            or(
+                map(literal("x"), |_| 1),
+                map(node, |(a, b): (usize, usize)| a + b),
+            )
+        });
+
+        assert_eq!(Ok(("", 3)), tree.parse("(x (x x))"));
+        assert!(tree.parse("(x)").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Recursive parser used while being defined")]
+    fn test_recursive_used_while_defined() {
+        recursive(|handle: RecursiveRef<&str, &str, (), String>| {
+            let _ = handle.parse("");
+            nothing
+        });
+    }
+}
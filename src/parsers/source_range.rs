@@ -1,5 +1,50 @@
+use crate::parsers::*;
 use std::ops::Range;
 
+/// Human readable position in a source, both line and column starting at 1.
+///
+/// The column counts characters rather than bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number within the line, starting at 1
+    pub column: usize,
+}
+
+/// Converts a byte offset in the source into a line and column.
+///
+/// Offsets past the end of the source are clamped to it, so the end of the source yields the
+/// position right after its last character. An offset inside a multibyte character points to
+/// that character.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let source = "first\nsecond";
+/// assert_eq!(LineCol { line: 1, column: 1 }, line_col(source, 0));
+/// assert_eq!(LineCol { line: 2, column: 3 }, line_col(source, 8));
+/// ```
+pub fn line_col(source: &str, offset: usize) -> LineCol {
+    let is_continuation = |b: &u8| (b & 0xC0) == 0x80;
+    let offset = offset.min(source.len());
+    let before = &source.as_bytes()[..offset];
+    let line_start = before.iter().rposition(|b| *b == b'\n').map_or(0, |idx| idx + 1);
+
+    // Characters that started before the offset, including the one it may be inside of
+    let started = before[line_start..]
+        .iter()
+        .filter(|b| !is_continuation(b))
+        .count();
+    let is_inside = source.as_bytes().get(offset).is_some_and(is_continuation);
+
+    LineCol {
+        line: before.iter().filter(|b| **b == b'\n').count() + 1,
+        column: if is_inside { started } else { started + 1 },
+    }
+}
+
 /// Given a source slice, attempts to retrieve a range corresponding to the slice which the
 /// implementer represents.
 ///
@@ -35,10 +80,7 @@ pub trait SourceRange {
     /// assert_eq!(Some(4), vec[1].source_range_start(source));
     /// ```
     fn source_range_start(&self, source: &str) -> Option<usize> {
-        match self.source_range(source) {
-            Some(r) => Some(r.start),
-            None => None,
-        }
+        self.source_range(source).map(|r| r.start)
     }
 
     /// Retrieve the end of the range, if the range is valid
@@ -53,29 +95,94 @@ pub trait SourceRange {
     /// assert_eq!(Some(6), vec[1].source_range_end(source));
     /// ```
     fn source_range_end(&self, source: &str) -> Option<usize> {
-        match self.source_range(source) {
-            Some(r) => Some(r.end),
-            None => None,
-        }
+        self.source_range(source).map(|r| r.end)
+    }
+
+    /// Retrieve the line and column of the start and the end of the range, if the range is
+    /// valid. The end is the position right after the last character of the range.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::parsers::*;
+    ///
+    /// let source = "Foo\nis a dog";
+    /// let parser = while_(left(identifier, maybe(whitespace)));
+    /// let (_, vec) = parser.parse(source).expect("Parse is ok");
+    /// assert_eq!(
+    ///     Some((LineCol { line: 2, column: 1 }, LineCol { line: 2, column: 3 })),
+    ///     vec[1].source_line_col(source)
+    /// );
+    /// ```
+    fn source_line_col(&self, source: &str) -> Option<(LineCol, LineCol)> {
+        self.source_range(source)
+            .map(|r| (line_col(source, r.start), line_col(source, r.end)))
+    }
+
+    /// Retrieve the slice of the source that the implementer represents, if the range is valid
+    /// and falls on character boundaries.
+    ///
+    /// # Example
+    /// ```
+    /// use parser_combinator::parsers::*;
+    ///
+    /// let source = "Foo is a dog";
+    /// let parser = while_(left(identifier, maybe(whitespace)));
+    /// let (_, vec) = parser.parse(source).expect("Parse is ok");
+    /// assert_eq!(Some("is"), vec[1].source_text(source));
+    /// ```
+    fn source_text<'s>(&self, source: &'s str) -> Option<&'s str> {
+        source.get(self.source_range(source)?)
     }
 }
 
 /// Implementation of SourceRange for slices. Allow easier manipulation from implementers of the
 /// trait for most cases.
-impl<'a> SourceRange for &'a str {
+impl SourceRange for &str {
     fn source_range(&self, source: &str) -> Option<Range<usize>> {
-        let start = self.as_ptr() as usize - source.as_ptr() as usize;
-        let end = start + self.as_bytes().len();
-
         let start_is_oob = self.as_ptr() < source.as_ptr();
-        let end_is_oob = (self.as_ptr() as usize + self.as_bytes().len())
-            > (source.as_ptr() as usize + source.as_bytes().len());
+        let end_is_oob = (self.as_ptr() as usize + self.len())
+            > (source.as_ptr() as usize + source.len());
 
+        // Checked before computing the offsets as they would otherwise underflow
         if start_is_oob || end_is_oob {
             return None;
-        } else {
-            Some(std::ops::Range { start, end })
         }
+
+        let start = self.as_ptr() as usize - source.as_ptr() as usize;
+        let end = start + self.len();
+        Some(std::ops::Range { start, end })
+    }
+}
+
+/// Applies the parser and returns its output along with the byte range of the input it consumed.
+///
+/// The range is relative to the input the parser was given. Offset it by the start of that input,
+/// as given by [`SourceRange`], to locate it in the original source.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = with_span(identifier);
+/// assert_eq!(Ok((" ", ("foo", 0..3))), parser.parse("foo "));
+///
+/// // Relative to the input of `with_span`, not to the source
+/// let parser = right(whitespace, with_span(identifier));
+/// assert_eq!(Ok(("", ("bar", 0..3))), parser.parse("  bar"));
+/// ```
+pub const fn with_span<'a, P, O, E>(
+    parser: P,
+) -> impl Parser<&'a str, &'a str, (O, Range<usize>), E>
+where
+    P: Parser<&'a str, &'a str, O, E>,
+{
+    move |input: &'a str| {
+        let (rem, out) = parser.parse(input)?;
+        // The remainder is always a suffix of the input
+        Ok((rem, (out, 0..input.len() - rem.len())))
     }
 }
 
@@ -105,3 +212,122 @@ impl<T> ToRangeOption<T> for Range<Option<T>> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_span() {
+        let source = "let x = 12;";
+        let number = take_while1(|c| c.is_ascii_digit());
+        let parser = and(
+            left(with_span(identifier), whitespace),
+            right(literal("x = "), with_span(number)),
+        );
+        let (_, ((_, keyword), (_, value))) = parser.parse(source).unwrap();
+        assert_eq!((0..3, 0..2), (keyword, value));
+
+        // Parsers consuming nothing yield an empty range
+        let blank = with_span(maybe(whitespace));
+        assert_eq!(Ok(("x = 12;", (None, 0..0))), blank.parse(&source[4..]));
+        let end = with_span(nothing::<_, String>);
+        assert_eq!(Ok(("", ((), 0..0))), end.parse(&source[11..]));
+
+        // Offsetting by the position of the input locates the span in the source
+        let digits = take_while1(|c| c.is_ascii_digit());
+        let (_, (_, span)) = with_span(digits).parse(&source[8..]).unwrap();
+        let start = (&source[8..]).source_range_start(source).unwrap();
+        assert_eq!("12", &source[start + span.start..start + span.end]);
+    }
+
+    #[test]
+    fn test_line_col_multiple_lines() {
+        let source = "ab\ncd\r\n\nef";
+        let at = |line, column| LineCol { line, column };
+
+        assert_eq!(at(1, 3), line_col(source, 2));
+        assert_eq!(at(2, 1), line_col(source, 3));
+        assert_eq!(at(2, 3), line_col(source, 5));
+        assert_eq!(at(3, 1), line_col(source, 7));
+        assert_eq!(at(4, 2), line_col(source, 9));
+    }
+
+    #[test]
+    fn test_line_col_bounds() {
+        let at = |line, column| LineCol { line, column };
+
+        assert_eq!(at(1, 1), line_col("", 0));
+        assert_eq!(at(1, 1), line_col("", 10));
+        assert_eq!(at(2, 1), line_col("a\n", 2));
+        assert_eq!(at(1, 4), line_col("abc", 3));
+        assert_eq!(at(1, 4), line_col("abc", 42));
+    }
+
+    #[test]
+    fn test_line_col_multibyte() {
+        let source = "é🦀\nx";
+        let at = |line, column| LineCol { line, column };
+
+        assert_eq!(at(1, 2), line_col(source, 2));
+        assert_eq!(at(1, 2), line_col(source, 3));
+        assert_eq!(at(1, 3), line_col(source, 6));
+        assert_eq!(at(2, 2), line_col(source, 8));
+    }
+
+    #[test]
+    fn test_source_line_col() {
+        let source = "first\nsecond third";
+        let slice = &source[13..18];
+
+        assert_eq!(
+            Some((
+                LineCol { line: 2, column: 8 },
+                LineCol { line: 2, column: 13 }
+            )),
+            slice.source_line_col(source)
+        );
+        let copy = String::from(source);
+        assert_eq!(None, slice.source_line_col(&copy));
+    }
+
+    #[test]
+    fn test_source_text() {
+        let source = "first\nsecond";
+        assert_eq!(Some("second"), (&source[6..]).source_text(source));
+        assert_eq!(Some(""), (&source[5..5]).source_text(source));
+        assert_eq!(None, "second".source_text(source));
+    }
+
+    #[test]
+    fn test_source_text_json_object() {
+        use crate::json::{json, Json};
+
+        let source = "[{\"a\": {\"b\": 1}, \"c\": \"d\"}]";
+        let Ok(Json::Array { elem, .. }) = json(source) else {
+            panic!("Expected an array");
+        };
+        assert!(matches!(elem[0], Json::Object { .. }));
+        assert_eq!(
+            Some("{\"a\": {\"b\": 1}, \"c\": \"d\"}"),
+            elem[0].source_text(source)
+        );
+    }
+
+    /// Range that does not fall on the character boundaries of the source.
+    struct Bytes(Range<usize>);
+
+    impl SourceRange for Bytes {
+        fn source_range(&self, _: &str) -> Option<Range<usize>> {
+            Some(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_source_text_invalid_range() {
+        let source = "é!";
+        assert_eq!(Some("é"), Bytes(0..2).source_text(source));
+        assert_eq!(None, Bytes(0..1).source_text(source));
+        assert_eq!(None, Bytes(2..10).source_text(source));
+    }
+}
@@ -0,0 +1,465 @@
+use crate::parsers::*;
+
+/// Parses zero or more elements separated by commas.
+///
+/// Whitespace is allowed around elements and commas. A trailing comma is not allowed.
+///
+/// # Result Conditions
+/// Succeeds with an empty vector if the first element fails. Fails if a comma is not followed by
+/// a valid element.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = comma_list(identifier);
+/// assert_eq!(Ok(("", vec!("a", "b", "c"))), parser.parse(" a, b ,c "));
+/// assert_eq!(Ok(("", vec!())), parser.parse(""));
+/// assert!(parser.parse("a, b,").is_err());
+/// ```
+pub const fn comma_list<'a, P, O>(elem: P) -> impl Parser<&'a str, &'a str, Vec<O>, String>
+where
+    P: Parser<&'a str, &'a str, O, String>,
+{
+    comma_list_with(elem, false)
+}
+
+/// Same as `comma_list`, but a single trailing comma is allowed if `allow_trailing` is set.
+///
+/// # Result Conditions
+/// Succeeds with an empty vector if the first element fails, a lone comma is never accepted.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = comma_list_with(identifier, true);
+/// assert_eq!(Ok(("", vec!("a", "b"))), parser.parse("a, b, "));
+/// assert_eq!(Ok((",", vec!())), parser.parse(","));
+/// assert!(parser.parse("a,,").is_err());
+/// ```
+pub const fn comma_list_with<'a, P, O>(
+    elem: P,
+    allow_trailing: bool,
+) -> impl Parser<&'a str, &'a str, Vec<O>, String>
+where
+    P: Parser<&'a str, &'a str, O, String>,
+{
+    comma_fold(elem, allow_trailing, Vec::new, |mut res, out| {
+        res.push(out);
+        res
+    })
+}
+
+/// Same grammar as `comma_list_with`, but folds the elements into an accumulator.
+///
+/// Allows parsing comma separated lists without allocating.
+pub(crate) const fn comma_fold<'a, P, O, A, INIT, FN>(
+    elem: P,
+    allow_trailing: bool,
+    init: INIT,
+    fold: FN,
+) -> impl Parser<&'a str, &'a str, A, String>
+where
+    P: Parser<&'a str, &'a str, O, String>,
+    INIT: Fn() -> A,
+    FN: Fn(A, O) -> A,
+{
+    let separator = middle(maybe(whitespace), literal(","), maybe(whitespace));
+
+    move |input: &'a str| {
+        let mut acc = init();
+        let (mut rem, _) = maybe(whitespace).parse(input)?;
+
+        match elem.parse(rem) {
+            Ok((first_rem, first_out)) => {
+                rem = first_rem;
+                acc = fold(acc, first_out);
+            }
+            Err(_) => return Ok((rem, acc)),
+        }
+
+        // Once a comma is consumed, an element must follow unless it is the trailing one
+        while let Ok((sep_rem, _)) = separator.parse(rem) {
+            match elem.parse(sep_rem) {
+                Ok((new_rem, out)) => {
+                    rem = new_rem;
+                    acc = fold(acc, out);
+                }
+                Err(_) if allow_trailing && separator.parse(sep_rem).is_err() => {
+                    rem = sep_rem;
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        let (rem, _) = maybe(whitespace).parse(rem)?;
+        Ok((rem, acc))
+    }
+}
+
+/// Applies the parser and returns the slice of the input it consumed, ignoring its output.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = recognize(and(identifier, and(literal("."), identifier)));
+/// assert_eq!(Ok((" tail", "foo.bar")), parser.parse("foo.bar tail"));
+/// ```
+pub const fn recognize<'a, P, O, E>(parser: P) -> impl Parser<&'a str, &'a str, &'a str, E>
+where
+    P: Parser<&'a str, &'a str, O, E>,
+{
+    move |input: &'a str| {
+        let (rem, _) = parser.parse(input)?;
+        // The remainder is always a suffix of the input
+        Ok((rem, &input[..input.len() - rem.len()]))
+    }
+}
+
+/// Applies the parser then consumes any whitespace following it.
+///
+/// Shorthand for `left(parser, maybe(whitespace))`, whatever the error type of the parser.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = and(token(identifier), token(literal("=")));
+/// assert_eq!(Ok(("1", ("x", "="))), parser.parse("x \t= \n1"));
+/// assert_eq!(Ok(("1", ("x", "="))), parser.parse("x=1"));
+/// ```
+pub const fn token<'a, P, O, E>(parser: P) -> impl Parser<&'a str, &'a str, O, E>
+where
+    P: Parser<&'a str, &'a str, O, E>,
+{
+    move |input: &'a str| {
+        let (rem, out) = parser.parse(input)?;
+        // Same definition of whitespace as the `whitespace` parser
+        Ok((rem.trim_start(), out))
+    }
+}
+
+/// Applies the parser but fails if it consumes more than `max_bytes` of input.
+///
+/// # Result Conditions
+/// Fails if the given parser fails or consumes more than `max_bytes` bytes. Otherwise succeeds.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = within(identifier, 3);
+/// assert_eq!(Ok((" bar", "foo")), parser.parse("foo bar"));
+/// assert!(parser.parse("foobar").is_err());
+/// ```
+///
+/// # Note
+/// The limit is checked once the given parser returns, the parser still receives the whole input.
+pub const fn within<'a, P, O>(
+    parser: P,
+    max_bytes: usize,
+) -> impl Parser<&'a str, &'a str, O, String>
+where
+    P: Parser<&'a str, &'a str, O, String>,
+{
+    move |input: &'a str| {
+        let (rem, out) = parser.parse(input)?;
+        let consumed = input.len() - rem.len();
+
+        if consumed > max_bytes {
+            Err(format!(
+                "Consumed {} bytes, exceeding the limit of {} bytes",
+                consumed, max_bytes
+            ))
+        } else {
+            Ok((rem, out))
+        }
+    }
+}
+
+/// Applies the parser and requires that only whitespace remains afterward.
+///
+/// Meant to wrap the top level parser of a grammar so that trailing garbage is not silently
+/// ignored.
+///
+/// # Result Conditions
+/// Fails if the given parser fails or if anything but whitespace is left in the remainder.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = complete(identifier);
+/// assert_eq!(Ok(("", "foo")), parser.parse("foo \n"));
+/// assert_eq!(Err("Unexpected trailing input".to_string()), parser.parse("foo bar"));
+/// ```
+pub const fn complete<'a, P, O>(parser: P) -> impl Parser<&'a str, &'a str, O, String>
+where
+    P: Parser<&'a str, &'a str, O, String>,
+{
+    let parser = left(parser, maybe(whitespace));
+
+    move |input: &'a str| match parser.parse(input)? {
+        ("", out) => Ok(("", out)),
+        _ => Err("Unexpected trailing input".into()),
+    }
+}
+
+/// Applies the parser and requires that it consumes the whole input.
+///
+/// Unlike `complete`, trailing whitespace is not skipped.
+///
+/// # Result Conditions
+/// Fails if the given parser fails or if anything is left in the remainder.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = all_consuming(identifier);
+/// assert_eq!(Ok(("", "foo")), parser.parse("foo"));
+/// assert_eq!(Err("Expected end of input".to_string()), parser.parse("foo "));
+/// ```
+pub const fn all_consuming<'a, P, O, E>(parser: P) -> impl Parser<&'a str, &'a str, O, E>
+where
+    P: Parser<&'a str, &'a str, O, E>,
+    E: From<String>,
+{
+    left(parser, map_err(eof, E::from))
+}
+
+/// Parses a `quote` delimited string in which `escape` prefixed characters are escaped.
+///
+/// Returns the raw content between the quotes, escape sequences are left untouched. When `quote`
+/// and `escape` are the same character, a doubled quote is an escaped quote (as in CSV).
+///
+/// # Result Conditions
+/// Fails if the input does not start with `quote` or if the closing `quote` is missing.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = quoted_string('"', '\\');
+/// assert_eq!(Ok((" tail", r#"say \"hi\""#)), parser.parse(r#""say \"hi\"" tail"#));
+/// assert!(parser.parse("\"unterminated").is_err());
+///
+/// let parser = quoted_string('\'', '\\');
+/// assert_eq!(Ok(("", r"it\'s")), parser.parse(r"'it\'s'"));
+/// assert!(parser.parse("\"wrong quote\"").is_err());
+/// ```
+pub const fn quoted_string<'a>(
+    quote: char,
+    escape: char,
+) -> impl Parser<&'a str, &'a str, &'a str, String> {
+    move |input: &'a str| {
+        let content = match input.strip_prefix(quote) {
+            Some(content) => content,
+            None => return Err(format!("Could not parse '{}'", quote)),
+        };
+
+        let mut chars = content.char_indices().peekable();
+        while let Some((idx, c)) = chars.next() {
+            let next_is_quote = matches!(chars.peek(), Some((_, next)) if *next == quote);
+
+            if c == escape && (c != quote || next_is_quote) {
+                // Skip the escaped character
+                if chars.next().is_none() {
+                    return Err("Unexpected end of stream after escape".into());
+                }
+            } else if c == quote {
+                return Ok((&content[idx + c.len_utf8()..], &content[..idx]));
+            }
+        }
+
+        Err(format!("Could not find closing '{}'", quote))
+    }
+}
+
+/// Parses a block of lines indented by more than `reference` columns.
+///
+/// Returns the lines of the block with their indentation but without their line ending, so that
+/// nested blocks can be parsed from them in turn. Blank lines are part of the block as long as
+/// an indented line follows them.
+///
+/// # Result Conditions
+/// Fails if the first line is not indented by more than `reference` columns. Otherwise stops
+/// before the first line that is not, which is left in the remainder.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = delimited_by_indent(0);
+/// assert_eq!(
+///     Ok(("end", vec!("  a", "", "    b"))),
+///     parser.parse("  a\n\n    b\nend")
+/// );
+/// assert!(parser.parse("end").is_err());
+/// ```
+pub const fn delimited_by_indent<'a>(
+    reference: usize,
+) -> impl Parser<&'a str, &'a str, Vec<&'a str>, String> {
+    move |input: &'a str| {
+        let mut lines = Vec::new();
+        let mut blank_lines = Vec::new();
+        let mut rem = input;
+        let mut next = input;
+
+        while !next.is_empty() {
+            let (line, after) = match next.find('\n') {
+                Some(idx) => (&next[..idx], &next[idx + 1..]),
+                None => (next, ""),
+            };
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if line.trim().is_empty() {
+                blank_lines.push(line);
+            } else if indent_level(line)?.1 > reference {
+                lines.append(&mut blank_lines);
+                lines.push(line);
+                rem = after;
+            } else {
+                break;
+            }
+            next = after;
+        }
+
+        if lines.is_empty() {
+            return Err(format!(
+                "Expected a line indented by more than {} columns",
+                reference
+            ));
+        }
+        Ok((rem, lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_matches_whitespace() {
+        let explicit = left(identifier, maybe(whitespace));
+        let trimmed = token(identifier);
+        for input in ["a", "a b", "a \t\r\n b", "a\u{a0}\u{2003}b", "1"] {
+            assert_eq!(explicit.parse(input), trimmed.parse(input), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_comma_list() {
+        let parser = comma_list(identifier);
+        assert_eq!(Ok(("", vec!("a"))), parser.parse("a"));
+        assert_eq!(Ok(("", vec!("a", "b"))), parser.parse("a,b"));
+        assert_eq!(Ok(("]", vec!("a", "b"))), parser.parse(" a , b ]"));
+        assert_eq!(Ok(("]", vec!())), parser.parse(" ]"));
+    }
+
+    #[test]
+    fn test_comma_list_trailing_comma() {
+        let parser = comma_list(identifier);
+        assert!(parser.parse("a,").is_err());
+        assert!(parser.parse("a, b , ]").is_err());
+    }
+
+    #[test]
+    fn test_comma_list_missing_comma() {
+        let parser = comma_list(identifier);
+        assert_eq!(Ok(("b", vec!("a"))), parser.parse("a b"));
+    }
+
+    #[test]
+    fn test_recognize() {
+        let digits = regex::Regex::new(r"\A\d+").unwrap();
+        let exponent = and(literal::<_, String>("e"), matching(&digits));
+        let number = recognize(and(matching(&digits), maybe(exponent)));
+        assert_eq!(Ok((",", "12e3")), number.parse("12e3,"));
+        assert_eq!(Ok(("e", "12")), number.parse("12e"));
+        assert_eq!(Ok(("", "")), recognize(comma_list(identifier)).parse(""));
+        assert!(recognize(identifier).parse("1").is_err());
+    }
+
+    #[test]
+    fn test_within() {
+        let input = "a".repeat(500);
+
+        assert_eq!(
+            Err("Consumed 500 bytes, exceeding the limit of 256 bytes".to_string()),
+            within(identifier, 256).parse(&input)
+        );
+        assert_eq!(Ok(("", &input[..])), within(identifier, 1000).parse(&input));
+        assert_eq!(Ok(("", &input[..])), within(identifier, 500).parse(&input));
+    }
+
+    #[test]
+    fn test_all_consuming() {
+        let parser = all_consuming(comma_list(identifier));
+        assert_eq!(Ok(("", vec!("a", "b"))), parser.parse("a, b "));
+        assert_eq!(
+            Err("Expected end of input".to_string()),
+            parser.parse("a, b 1")
+        );
+        assert!(parser.parse("a,").is_err());
+    }
+
+    #[test]
+    fn test_quoted_string() {
+        let parser = quoted_string('"', '\\');
+        assert_eq!(Ok(("", "")), parser.parse("\"\""));
+        assert_eq!(Ok(("", "café \\\\")), parser.parse("\"café \\\\\""));
+        assert!(parser.parse("\"ends on escape\\").is_err());
+        assert!(parser.parse("no quote").is_err());
+    }
+
+    #[test]
+    fn test_quoted_string_doubled_quote() {
+        let parser = quoted_string('"', '"');
+        assert_eq!(
+            Ok((",b", "a \"\"quoted\"\" word")),
+            parser.parse("\"a \"\"quoted\"\" word\",b")
+        );
+    }
+
+    #[test]
+    fn test_complete() {
+        let parser = complete(comma_list(identifier));
+        assert_eq!(Ok(("", vec!("a", "b"))), parser.parse("a, b"));
+        assert_eq!(Ok(("", vec!())), parser.parse(""));
+        assert_eq!(
+            Err("Unexpected trailing input".to_string()),
+            parser.parse("a, b 12")
+        );
+        assert!(parser.parse("a, b,").is_err());
+    }
+
+    #[test]
+    fn test_delimited_by_indent() {
+        let source = "if x:\n    foo\n    bar\nbaz\n";
+        let block = right(left(literal("if x:"), one_newline), delimited_by_indent(0));
+        assert_eq!(Ok(("baz\n", vec!("    foo", "    bar"))), block.parse(source));
+    }
+
+    #[test]
+    fn test_delimited_by_indent_nested() {
+        let parser = delimited_by_indent(2);
+        assert_eq!(
+            Ok(("\r\n  b\r\n", vec!("    a"))),
+            parser.parse("    a\r\n\r\n  b\r\n")
+        );
+        assert_eq!(
+            Err("Expected a line indented by more than 2 columns".to_string()),
+            parser.parse("  b\n    c")
+        );
+    }
+}
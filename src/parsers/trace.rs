@@ -0,0 +1,145 @@
+use crate::parsers::*;
+use std::cell::Cell;
+use std::fmt::Debug;
+
+thread_local! {
+    /// Number of traced parsers currently running on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Same as `trace`, but hands each line to the given closure instead of printing it.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+/// use std::cell::RefCell;
+///
+/// let lines = RefCell::new(Vec::new());
+/// let sink = |line: &str| lines.borrow_mut().push(line.to_string());
+/// let parser = trace_with("ident", identifier, sink);
+/// parser.parse("foo bar").unwrap();
+/// assert_eq!(vec!("ident <- \"foo bar\"", "ident -> Ok, 3 bytes"), lines.take());
+/// ```
+///
+/// # Note
+/// Only available with the `debug` feature.
+pub const fn trace_with<'a, P, O, E, FN>(
+    label: &'static str,
+    parser: P,
+    sink: FN,
+) -> impl Parser<&'a str, &'a str, O, E>
+where
+    P: Parser<&'a str, &'a str, O, E>,
+    E: Debug,
+    FN: Fn(&str),
+{
+    move |input: &'a str| {
+        let depth = DEPTH.get();
+        let indent = "  ".repeat(depth);
+        let prefix: String = input.chars().take(16).collect();
+        sink(&format!("{}{} <- {:?}", indent, label, prefix));
+
+        DEPTH.set(depth + 1);
+        let res = parser.parse(input);
+        DEPTH.set(depth);
+
+        match &res {
+            Ok((rem, _)) => sink(&format!(
+                "{}{} -> Ok, {} bytes",
+                indent,
+                label,
+                input.len() - rem.len()
+            )),
+            Err(err) => sink(&format!("{}{} -> Err({:?})", indent, label, err)),
+        }
+        res
+    }
+}
+
+/// Used for debugging, prints the parser's label and input before applying it, then whether it
+/// succeeded.
+///
+/// Lines are printed to the standard error, indented by how many traced parsers are running
+/// around this one, so that the recursion of a grammar can be followed.
+///
+/// # Result Conditions
+/// Same as input parser.
+///
+/// # Example
+/// ```
+/// use parser_combinator::parsers::*;
+///
+/// let parser = trace("ident", identifier);
+/// assert_eq!(Ok((" bar", "foo")), parser.parse("foo bar"));
+/// ```
+///
+/// # Note
+/// Only available with the `debug` feature.
+pub const fn trace<'a, P, O, E>(
+    label: &'static str,
+    parser: P,
+) -> impl Parser<&'a str, &'a str, O, E>
+where
+    P: Parser<&'a str, &'a str, O, E>,
+    E: Debug,
+{
+    trace_with(label, parser, |line| eprintln!("{}", line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_trace_nesting() {
+        let lines = RefCell::new(Vec::new());
+        let sink = |line: &str| lines.borrow_mut().push(line.to_string());
+
+        let list = recursive(|list| {
+            let item = trace_with("ident", identifier, sink);
+            let nested = middle(literal("["), list, literal("]"));
+            trace_with("list", or(item, nested), sink)
+        });
+        assert_eq!(Ok(("", "a")), list.parse("[[a]]"));
+
+        assert_eq!(
+            vec!(
+                "list <- \"[[a]]\"",
+                "  ident <- \"[[a]]\"",
+                "  ident -> Err(\"Could not parse '\\\\A[a-zA-Z_][a-zA-Z0-9_]*'\")",
+                "  list <- \"[a]]\"",
+                "    ident <- \"[a]]\"",
+                "    ident -> Err(\"Could not parse '\\\\A[a-zA-Z_][a-zA-Z0-9_]*'\")",
+                "    list <- \"a]]\"",
+                "      ident <- \"a]]\"",
+                "      ident -> Ok, 1 bytes",
+                "    list -> Ok, 1 bytes",
+                "  list -> Ok, 3 bytes",
+                "list -> Ok, 5 bytes",
+            ),
+            lines.take()
+        );
+    }
+
+    #[test]
+    fn test_trace_depth_restored() {
+        let lines = RefCell::new(Vec::new());
+        let sink = |line: &str| lines.borrow_mut().push(line.to_string());
+
+        let inner = trace_with("inner", literal::<_, String>("x"), sink);
+        let parser = trace_with("outer", inner, sink);
+        assert!(parser.parse("y").is_err());
+        assert_eq!(0, DEPTH.get());
+
+        let parser = trace_with("after", literal::<_, String>("y"), sink);
+        parser.parse("y").unwrap();
+        assert_eq!(
+            Some(&"after -> Ok, 1 bytes".to_string()),
+            lines.borrow().last()
+        );
+    }
+}